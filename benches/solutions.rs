@@ -0,0 +1,46 @@
+use aoc_rust::{registry, DynSolution};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::fs::read_to_string;
+
+/// one group per problem with a benchmark for each step. days without an input are skipped
+fn bench_solution(c: &mut Criterion, solution: &dyn DynSolution) {
+    let problem = solution.problem();
+    let path = problem.input_path();
+    let input = match read_to_string(&path) {
+        Ok(input) => input,
+        Err(_) => {
+            eprintln!(
+                "Skipping {} day {:02}, no input at \"{}\"",
+                problem.year,
+                problem.day,
+                path.as_ref().display()
+            );
+            return;
+        }
+    };
+    let parsed = match solution.parse_input(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!(
+                "Skipping {} day {:02}, unable to parse input. {}",
+                problem.year, problem.day, e
+            );
+            return;
+        }
+    };
+
+    let mut group = c.benchmark_group(format!("{}/day{:02}", problem.year, problem.day));
+    group.bench_function("parse", |b| b.iter(|| solution.parse_input(&input)));
+    group.bench_function("part 1", |b| b.iter(|| solution.run_pt1(parsed.as_ref())));
+    group.bench_function("part 2", |b| b.iter(|| solution.run_pt2(parsed.as_ref())));
+    group.finish();
+}
+
+fn solutions(c: &mut Criterion) {
+    for solution in registry() {
+        bench_solution(c, solution.as_ref());
+    }
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
@@ -0,0 +1,416 @@
+//! the recorded answer to each part of each input, so anyone's inputs can be checked.
+//! examples have their own answers in the same format
+use crate::{
+    error::{Error, ParseError},
+    DynSolution, Solution,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    fs::{self, read_to_string},
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+pub const DEFAULT_PATH: &str = "./answers.toml";
+
+/// the answers to the examples, keyed by example id rather than input id
+pub const EXAMPLES_PATH: &str = "./examples.toml";
+
+/// the id of the input at `Problem::input_path`
+pub const DEFAULT_INPUT: &str = "default";
+
+/// year, day, part then input id
+type Key = (usize, usize, u8, String);
+
+/// a toml table per day, e.g. `[2022.7]`, holding `<part>.<input id> = <answer>`.
+/// `skip = ["<input id>"]` in a day's table marks inputs that are never checked
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<Key, String>,
+    /// year, day then input id
+    skipped: BTreeSet<(usize, usize, String)>,
+}
+
+impl Answers {
+    /// reads the answers at `path`. a missing file records no answers
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        match fs::read_to_string(&path) {
+            Ok(data) => data.parse().map_err(|e| Error::from(e).with_path(path)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get(&self, year: usize, day: usize, part: u8, input: &str) -> Option<&str> {
+        self.answers
+            .get(&(year, day, part, input.to_owned()))
+            .map(String::as_str)
+    }
+
+    /// whether the input is listed in its day's `skip`
+    pub fn skipped(&self, year: usize, day: usize, input: &str) -> bool {
+        self.skipped.contains(&(year, day, input.to_owned()))
+    }
+
+    /// the ids of every input of a day with at least one recorded answer
+    pub fn inputs(&self, year: usize, day: usize) -> Vec<&str> {
+        let mut ids: Vec<&str> = self
+            .answers
+            .keys()
+            .filter(|k| (k.0, k.1) == (year, day))
+            .map(|k| k.3.as_str())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Value = s.parse().map_err(|e: toml::de::Error| {
+            let (line, col) = e.line_col().unwrap_or_default();
+            let snippet = s.lines().nth(line).unwrap_or_default();
+            ParseError::located(line + 1, col + 1, 1, snippet, e)
+        })?;
+
+        let mut answers = BTreeMap::new();
+        let mut skipped = BTreeSet::new();
+        for (year, days) in entries(&table, "")? {
+            for (day, parts) in entries(days, year)? {
+                let at = format!("{}.{}", year, day);
+                for (part, inputs) in entries(parts, &at)? {
+                    let at = format!("{}.{}", at, part);
+                    if part == "skip" {
+                        let (year, day) = year
+                            .parse()
+                            .ok()
+                            .zip(day.parse().ok())
+                            .ok_or_else(|| ParseError::UnexpectedKey(at.clone()))?;
+                        for input in skip_list(inputs, &at)? {
+                            skipped.insert((year, day, input.to_owned()));
+                        }
+                        continue;
+                    }
+                    for (input, answer) in entries(inputs, &at)? {
+                        let at = format!("{}.{}", at, input);
+                        let key = key(year, day, part, input)
+                            .ok_or_else(|| ParseError::UnexpectedKey(at.clone()))?;
+                        let answer = match answer {
+                            toml::Value::String(s) => s.clone(),
+                            toml::Value::Integer(n) => n.to_string(),
+                            _ => return Err(ParseError::UnexpectedKey(at)),
+                        };
+                        answers.insert(key, answer);
+                    }
+                }
+            }
+        }
+        Ok(Self { answers, skipped })
+    }
+}
+
+fn key(year: &str, day: &str, part: &str, input: &str) -> Option<Key> {
+    let part = part.parse().ok().filter(|p| (1..=2).contains(p))?;
+    Some((
+        year.parse().ok()?,
+        day.parse().ok()?,
+        part,
+        input.to_owned(),
+    ))
+}
+
+/// the entries of a table, which `at` names in any error
+fn entries<'a>(
+    value: &'a toml::Value,
+    at: &str,
+) -> Result<impl Iterator<Item = (&'a str, &'a toml::Value)>, ParseError> {
+    match value {
+        toml::Value::Table(table) => Ok(table.iter().map(|(k, v)| (k.as_str(), v))),
+        _ => Err(ParseError::UnexpectedKey(at.to_owned())),
+    }
+}
+
+/// the input ids of a `skip` list
+fn skip_list<'a>(value: &'a toml::Value, at: &str) -> Result<Vec<&'a str>, ParseError> {
+    let unexpected = || ParseError::UnexpectedKey(at.to_owned());
+    value
+        .as_array()
+        .ok_or_else(unexpected)?
+        .iter()
+        .map(|input| input.as_str().ok_or_else(unexpected))
+        .collect()
+}
+
+/// how one part fared against its recorded answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        found: String,
+    },
+    /// there is no answer recorded for the part
+    Missing,
+    /// the input couldn't be read or parsed
+    Error(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // pad, so the outcome lines up in the verify table
+        f.pad(match self {
+            Outcome::Pass => "pass",
+            Outcome::Fail { .. } => "FAIL",
+            Outcome::Missing => "missing",
+            Outcome::Error(_) => "ERROR",
+        })
+    }
+}
+
+/// both parts of a solution run against one input
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub year: usize,
+    pub day: usize,
+    pub input: String,
+    pub parts: [Outcome; 2],
+}
+
+/// runs `solution` against each of `files`, usually `Problem::inputs` or `Problem::examples`.
+/// files without any recorded answer are not run, and report both parts as missing.
+/// skipped files are left out
+pub fn verify(
+    solution: &dyn DynSolution,
+    answers: &Answers,
+    files: Vec<(String, PathBuf)>,
+) -> Vec<Check> {
+    let problem = solution.problem();
+    files
+        .into_iter()
+        .filter(|(input, _)| !answers.skipped(problem.year, problem.day, input))
+        .map(|(input, path)| {
+            let expected = [1, 2].map(|part| answers.get(problem.year, problem.day, part, &input));
+            let parts = match expected {
+                [None, None] => [Outcome::Missing, Outcome::Missing],
+                _ => run(solution, &path, expected),
+            };
+            Check {
+                year: problem.year,
+                day: problem.day,
+                input,
+                parts,
+            }
+        })
+        .collect()
+}
+
+fn run(solution: &dyn DynSolution, path: &Path, expected: [Option<&str>; 2]) -> [Outcome; 2] {
+    let parsed = read_to_string(path)
+        .map_err(Error::from)
+        .and_then(|data| solution.parse_input(&data))
+        .map_err(|e| e.with_path(path));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return [(); 2].map(|_| Outcome::Error(e.to_string())),
+    };
+    let [pt1, pt2] = expected;
+    [
+        pt1.map(|expected| (expected, solution.run_pt1(parsed.as_ref()))),
+        pt2.map(|expected| (expected, solution.run_pt2(parsed.as_ref()))),
+    ]
+    .map(|part| match part {
+        None => Outcome::Missing,
+        // a multi line answer may keep the trailing newline of a toml string
+        Some((expected, found)) if expected.trim_end() == found.trim_end() => Outcome::Pass,
+        Some((expected, found)) => Outcome::Fail {
+            expected: expected.to_owned(),
+            found,
+        },
+    })
+}
+
+/// panics unless `part` of the example with suffix `id` gives its answer in `examples.toml`.
+/// the tests generated by [`examples!`](crate::examples) call this
+pub fn check_example<S: Solution>(id: &str, part: u8) {
+    let problem = S::PROBLEM;
+    let expectations = Answers::load(EXAMPLES_PATH).unwrap();
+    let expected = expectations
+        .get(problem.year, problem.day, part, id)
+        .unwrap_or_else(|| {
+            panic!(
+                "{} has no answer for {} day {:02} part {} example {}",
+                EXAMPLES_PATH, problem.year, problem.day, part, id
+            )
+        });
+    let data = read_to_string(problem.example_path(id)).unwrap();
+    let parsed = S::parse(&data).unwrap();
+    let found = match part {
+        1 => S::pt1(&parsed).to_string(),
+        _ => S::pt2(&parsed).to_string(),
+    };
+    assert_eq!(found.trim_end(), expected.trim_end());
+}
+
+/// panics if an example of `S` on disk has an answer in `examples.toml` for a part
+/// whose list in [`examples!`](crate::examples) leaves it out, so no answer goes unchecked
+pub fn check_listed<S: Solution>(pt1: &[&str], pt2: &[&str]) {
+    let problem = S::PROBLEM;
+    let answers = Answers::load(EXAMPLES_PATH).unwrap();
+    for (id, _) in problem.examples() {
+        for (part, listed) in [(1, pt1), (2, pt2)] {
+            let answered = answers.get(problem.year, problem.day, part, &id).is_some();
+            assert!(
+                !answered || listed.contains(&id.as_str()),
+                "{} day {:02} example {} has a part {} answer in {}, but isn't in examples!",
+                problem.year,
+                problem.day,
+                id,
+                part,
+                EXAMPLES_PATH
+            );
+        }
+    }
+}
+
+/// a test per example and part, checking the answers recorded in `examples.toml`.
+/// the examples are named by the suffix of their file, e.g. `_1` for `day12_1.txt`.
+/// one more test fails if an answered example is missing from the lists
+/// ```ignore
+/// crate::examples!(Day12, pt1: [_1, _2, _3], pt2: [_1]);
+/// ```
+#[macro_export]
+macro_rules! examples {
+    ($solution:ident, pt1: [$($pt1:ident),* $(,)?], pt2: [$($pt2:ident),* $(,)?] $(,)?) => {
+        #[cfg(test)]
+        mod examples {
+            mod pt1 {
+                $(
+                    #[test]
+                    fn $pt1() {
+                        $crate::answers::check_example::<super::super::$solution>(stringify!($pt1), 1);
+                    }
+                )*
+            }
+
+            mod pt2 {
+                $(
+                    #[test]
+                    fn $pt2() {
+                        $crate::answers::check_example::<super::super::$solution>(stringify!($pt2), 2);
+                    }
+                )*
+            }
+
+            #[test]
+            fn listed() {
+                $crate::answers::check_listed::<super::$solution>(
+                    &[$(stringify!($pt1)),*],
+                    &[$(stringify!($pt2)),*],
+                );
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    const ANSWERS: &str = r#"
+[2022.7]
+1.default = 2104783
+2.default = "5883165"
+1.bob = 95437
+
+[2022.9]
+1._1 = 13
+skip = ["_2"]
+
+[2022.10]
+2.default = """
+##..##
+#.##.#
+"""
+"#;
+
+    #[test]
+    fn parse() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(answers.get(2022, 7, 1, "default"), Some("2104783"));
+        assert_eq!(answers.get(2022, 7, 2, "default"), Some("5883165"));
+        assert_eq!(answers.get(2022, 7, 2, "bob"), None);
+        assert_eq!(
+            answers.get(2022, 10, 2, "default"),
+            Some("##..##\n#.##.#\n")
+        );
+        assert_eq!(answers.inputs(2022, 7), vec!["bob", "default"]);
+        assert!(answers.inputs(2022, 8).is_empty());
+        assert!(answers.skipped(2022, 9, "_2"));
+        assert!(!answers.skipped(2022, 9, "_1"));
+    }
+
+    #[test]
+    fn malformed() {
+        let err = "[2022.7]\n3.default = 1".parse::<Answers>().unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedKey(at) if at == "2022.7.3.default"));
+        let err = "[2022.7]\n1 = 1".parse::<Answers>().unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedKey(at) if at == "2022.7.1"));
+        let err = "[2022.7]\n1.default = 1.5".parse::<Answers>().unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedKey(_)));
+        let err = "[2022.7]\nskip = \"_1\"".parse::<Answers>().unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedKey(at) if at == "2022.7.skip"));
+        let err = "[2022.7]\n1.default = ".parse::<Answers>().unwrap_err();
+        assert!(err.to_string().contains("2 | 1.default = "));
+    }
+
+    /// every example on disk is either answered, and so listed in its day's `examples!`, or skipped
+    #[test]
+    fn every_example_answered() {
+        let answers = Answers::load(EXAMPLES_PATH).unwrap();
+        for solution in registry() {
+            let problem = solution.problem();
+            for (id, _) in problem.examples() {
+                assert!(
+                    answers.skipped(problem.year, problem.day, &id)
+                        || [1, 2].iter().any(|&part| answers
+                            .get(problem.year, problem.day, part, &id)
+                            .is_some()),
+                    "{} day {:02} example {} has no answers in {}",
+                    problem.year,
+                    problem.day,
+                    id,
+                    EXAMPLES_PATH
+                );
+            }
+        }
+    }
+
+    /// every input on disk against the checked in answers
+    #[test]
+    fn recorded_answers() {
+        let answers = Answers::load(DEFAULT_PATH).unwrap();
+        let checks: Vec<Check> = registry()
+            .iter()
+            .flat_map(|s| verify(s.as_ref(), &answers, s.problem().inputs()))
+            .collect();
+        for check in checks {
+            for (part, outcome) in check.parts.iter().enumerate() {
+                assert!(
+                    matches!(outcome, Outcome::Pass | Outcome::Missing),
+                    "{} day {:02} part {} input {}: {:?}",
+                    check.year,
+                    check.day,
+                    part + 1,
+                    check.input,
+                    outcome
+                );
+            }
+        }
+    }
+}
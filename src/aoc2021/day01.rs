@@ -1,13 +1,13 @@
 /// problem: https://adventofcode.com/2021/day/1
 /// input: "https://adventofcode.com/2021/day/1/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{error::Error, Problem, Solution};
+pub const P: Problem = Problem {
     year: 2021,
     day: 1,
     name: "Sonar Sweep",
 };
 
-pub fn basic_solution(data: Vec<usize>) -> usize {
+pub fn basic_solution(data: &[usize]) -> usize {
     let increments = data
         .windows(2)
         .map(|window| usize::from(window[1] > window[0]))
@@ -15,7 +15,7 @@ pub fn basic_solution(data: Vec<usize>) -> usize {
     increments
 }
 
-pub fn n_window_solution(data: Vec<usize>, window_size: usize) -> usize {
+pub fn n_window_solution(data: &[usize], window_size: usize) -> usize {
     let increments = data
         .windows(window_size + 1)
         .map(|window| usize::from(window[window_size] > window[0]))
@@ -23,49 +23,42 @@ pub fn n_window_solution(data: Vec<usize>, window_size: usize) -> usize {
     increments
 }
 
-#[cfg(test)]
-mod test {
-    use crate::read_to_one_per_line;
+pub fn parse(input: &str) -> Result<Vec<usize>, Error> {
+    crate::parse_one_per_line(input)
+}
 
-    use super::*;
+pub struct Day01;
 
-    #[test]
-    fn basic() {
-        let data = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
-        let expected = 7;
+impl Solution for Day01 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<usize>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-        assert_eq!(basic_solution(data), expected);
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn basic_2() {
-        let data = read_to_one_per_line::<usize>(P.example_path("_1"))
-            .ok()
-            .unwrap();
-        let expected = 7;
-        assert_eq!(basic_solution(data), expected);
+    fn pt1(depths: &Self::Parsed) -> Self::OutputPt1 {
+        basic_solution(depths)
     }
 
-    #[test]
-    fn input_1() {
-        let data = read_to_one_per_line::<usize>(P.input_path()).ok().unwrap();
-        let expected = 1581;
-        assert_eq!(basic_solution(data), expected);
+    fn pt2(depths: &Self::Parsed) -> Self::OutputPt2 {
+        n_window_solution(depths, 3)
     }
+}
 
-    #[test]
-    fn input_1_method_2() {
-        let data = read_to_one_per_line::<usize>(P.input_path()).ok().unwrap();
-        let expected = 1581;
-        let window_size = 1;
-        assert_eq!(n_window_solution(data, window_size), expected);
-    }
+crate::examples!(Day01, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
     #[test]
-    fn input_2_method_2_window_3() {
-        let data = read_to_one_per_line::<usize>(P.input_path()).ok().unwrap();
-        let expected = 1618;
-        let window_size = 3;
-        assert_eq!(n_window_solution(data, window_size), expected);
+    fn basic() {
+        let data = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let expected = 7;
+
+        assert_eq!(basic_solution(&data), expected);
     }
 }
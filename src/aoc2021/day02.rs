@@ -1,7 +1,7 @@
 /// problem: https://adventofcode.com/2021/day/2
 /// input: "https://adventofcode.com/2021/day/2/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{Problem, Solution};
+pub const P: Problem = Problem {
     year: 2021,
     day: 2,
     name: "Dive!",
@@ -22,32 +22,25 @@ pub struct Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Error> {
-        match s.split_once(' ') {
-            Some((direction, distance)) => Ok(Instruction {
-                direction: match direction {
-                    "forward" => Direction::Forward,
-                    "down" => Direction::Down,
-                    "up" => Direction::Up,
-                    _ => {
-                        panic!("no matching direction")
-                    }
-                },
-                distance: match distance.parse::<usize>() {
-                    Ok(d) => d,
-                    Err(_) => return Err(Error::ParseError(ParseError::ExpectedDigit)),
-                },
-            }),
-            None => Err(Error::ParseError(ParseError::InvalidSequence(
-                "unable to split on ' '",
-            ))),
-        }
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (direction, distance) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::UnexpectedToken(s.to_owned()))?;
+        Ok(Instruction {
+            direction: match direction {
+                "forward" => Direction::Forward,
+                "down" => Direction::Down,
+                "up" => Direction::Up,
+                _ => return Err(ParseError::UnexpectedToken(direction.to_owned())),
+            },
+            distance: distance.parse()?,
+        })
     }
 }
 
-pub fn part_1_solution(data: Vec<Instruction>) -> usize {
+pub fn part_1_solution(data: &[Instruction]) -> usize {
     let mut horizontal: usize = 0;
     let mut depth: usize = 0;
     for instruction in data {
@@ -60,7 +53,7 @@ pub fn part_1_solution(data: Vec<Instruction>) -> usize {
     horizontal * depth
 }
 
-pub fn part_2_solution(data: Vec<Instruction>) -> usize {
+pub fn part_2_solution(data: &[Instruction]) -> usize {
     let mut aim: i32 = 0;
     let mut horizontal: usize = 0;
     let mut depth: usize = 0;
@@ -77,6 +70,33 @@ pub fn part_2_solution(data: Vec<Instruction>) -> usize {
     horizontal * depth
 }
 
+pub fn parse(input: &str) -> Result<Vec<Instruction>, Error> {
+    crate::parse_one_per_line(input)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<Instruction>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
+    }
+
+    fn pt1(instructions: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(instructions)
+    }
+
+    fn pt2(instructions: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(instructions)
+    }
+}
+
+crate::examples!(Day02, pt1: [_1], pt2: [_1]);
+
 #[cfg(test)]
 mod test {
     use crate::read_to_one_per_line;
@@ -112,41 +132,36 @@ mod test {
             },
         ];
         let expected = 150;
-        assert_eq!(part_1_solution(data), expected);
+        assert_eq!(part_1_solution(&data), expected);
     }
 
     #[test]
     fn example_import() -> Result<(), Error> {
         let data = read_to_one_per_line::<Instruction>(P.example_path("_1"))?;
         let expected = 150;
-        assert_eq!(part_1_solution(data), expected);
+        assert_eq!(part_1_solution(&data), expected);
         Ok(())
     }
 
     #[test]
-    fn input_part1() {
-        let data = read_to_one_per_line::<Instruction>(P.input_path())
-            .ok()
-            .unwrap();
-        let expected = 1924923;
-        assert_eq!(part_1_solution(data), expected);
-    }
-
-    #[test]
-    fn example_part2() {
-        let data = read_to_one_per_line::<Instruction>(P.example_path("_1"))
-            .ok()
-            .unwrap();
-        let expected = 900;
-        assert_eq!(part_2_solution(data), expected);
-    }
-
-    #[test]
-    fn input_part2() {
-        let data = read_to_one_per_line::<Instruction>(P.input_path())
-            .ok()
-            .unwrap();
-        let expected = 1982495697;
-        assert_eq!(part_2_solution(data), expected);
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(matches!(
+            parse(&input),
+            Err(Error::ParseError(ParseError::Located { location, .. })) if location.line == 2
+        ));
+        let mut lines = input.lines().skip(1).map(Instruction::from_str);
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedToken(d))) if d == "backward"
+        ));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::ParseIntError(_)))
+        ));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedToken(l))) if l == "up"
+        ));
     }
 }
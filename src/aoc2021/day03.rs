@@ -1,12 +1,15 @@
 /// problem: https://adventofcode.com/2021/day/3
 /// input: "https://adventofcode.com/2021/day/3/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 3,
     name: "Binary Diagnostic",
 };
-pub fn part_1_solution(input: Vec<i32>, n_bits: usize) -> i32 {
+pub fn part_1_solution(input: &[i32], n_bits: usize) -> i32 {
     let mut bit_counts = (0..n_bits).map(|_| 0).collect::<Vec<i32>>();
 
     for i in input.iter() {
@@ -68,12 +71,65 @@ fn filter_by_bit(mut input: Vec<i32>, max_bit_index: usize, do_keep: bool) -> i3
 
 /// O(mxn)
 /// m: 2*number of bits
-pub fn part_2_solution(input: Vec<i32>, n_bits: usize) -> i32 {
-    let oxygen_rating = filter_by_bit(input.clone(), n_bits, true);
-    let c02_scrubber_rating = filter_by_bit(input, n_bits, false);
+pub fn part_2_solution(input: &[i32], n_bits: usize) -> i32 {
+    let oxygen_rating = filter_by_bit(input.to_vec(), n_bits, true);
+    let c02_scrubber_rating = filter_by_bit(input.to_vec(), n_bits, false);
     oxygen_rating * c02_scrubber_rating
 }
 
+/// returns the diagnostic numbers and the number of bits in each.
+/// every number has to be as wide as the first
+pub fn parse(input: &str) -> Result<(Vec<i32>, usize), Error> {
+    let n_bits = input
+        .lines()
+        .next()
+        .ok_or(ParseError::UnexpectedEnd)?
+        .chars()
+        .count();
+    let values = input
+        .lines()
+        .enumerate()
+        .map(|(i, binary_string)| {
+            let found = binary_string.chars().count();
+            let value = if found == n_bits {
+                i32::from_str_radix(binary_string, 2).map_err(ParseError::from)
+            } else {
+                Err(ParseError::RaggedRow {
+                    expected: n_bits,
+                    found,
+                })
+            };
+            value.map_err(|e| ParseError::located(i + 1, 1, found, binary_string, e).into())
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok((values, n_bits))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const PROBLEM: Problem = P;
+    type Parsed = (Vec<i32>, usize);
+    type OutputPt1 = i32;
+    type OutputPt2 = i32;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
+    }
+
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        let (values, n_bits) = parsed;
+        part_1_solution(values, *n_bits)
+    }
+
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        let (values, n_bits) = parsed;
+        part_2_solution(values, *n_bits)
+    }
+}
+
+crate::examples!(Day03, pt1: [_1], pt2: [_1]);
+
 #[cfg(test)]
 mod test {
     use crate::read_to_one_per_line;
@@ -87,68 +143,7 @@ mod test {
             0b11001, 0b00010, 0b01010,
         ];
         let expected = 198;
-        assert_eq!(expected, part_1_solution(input, 5))
-    }
-
-    #[test]
-    fn example_part1_from_file() {
-        let raw_input = match read_to_one_per_line::<String>(P.example_path("_1")) {
-            Ok(data) => data,
-            Err(_) => panic!("missing file"),
-        };
-        let bit_count = raw_input[0].chars().count();
-        let input = raw_input
-            .into_iter()
-            .map(|binary_string| {
-                i32::from_str_radix(&binary_string, 2).expect("Not a binary number!")
-            })
-            .collect::<Vec<i32>>();
-        let expected = 198;
-        assert_eq!(expected, part_1_solution(input, bit_count))
-    }
-
-    #[test]
-    fn input_part1() {
-        let raw_input = read_to_one_per_line::<String>(P.input_path()).ok().unwrap();
-        let bit_count = raw_input[0].chars().count();
-        let input = raw_input
-            .into_iter()
-            .map(|binary_string| {
-                i32::from_str_radix(&binary_string, 2).expect("Not a binary number!")
-            })
-            .collect::<Vec<i32>>();
-        let expected = 1071734;
-        assert_eq!(expected, part_1_solution(input, bit_count))
-    }
-
-    #[test]
-    fn example_part2_from_file() {
-        let raw_input = read_to_one_per_line::<String>(P.example_path("_1"))
-            .ok()
-            .unwrap();
-        let bit_count = raw_input[0].chars().count();
-        let input = raw_input
-            .into_iter()
-            .map(|binary_string| {
-                i32::from_str_radix(&binary_string, 2).expect("Not a binary number!")
-            })
-            .collect::<Vec<i32>>();
-        let expected = 230;
-        assert_eq!(expected, part_2_solution(input, bit_count))
-    }
-
-    #[test]
-    fn input_part2_from_file() {
-        let raw_input = read_to_one_per_line::<String>(P.input_path()).ok().unwrap();
-        let bit_count = raw_input[0].chars().count();
-        let input = raw_input
-            .into_iter()
-            .map(|binary_string| {
-                i32::from_str_radix(&binary_string, 2).expect("Not a binary number!")
-            })
-            .collect::<Vec<i32>>();
-        let expected = 6124992;
-        assert_eq!(expected, part_2_solution(input, bit_count))
+        assert_eq!(expected, part_1_solution(&input, 5))
     }
 
     #[test]
@@ -173,7 +168,7 @@ mod test {
         ];
 
         println!("{:?}", res);
-        // assert_eq!(expected, part_1_solution(input, bit_count))
+        // assert_eq!(expected, part_1_solution(&input, bit_count))
     }
     #[test]
     fn testing_filter() {
@@ -192,6 +187,22 @@ mod test {
         // println!("{:?}", oxygen);
         let c02 = filter_by_bit(input.clone(), bit_count, false);
         println!("{:?}", c02);
-        // assert_eq!(expected, part_1_solution(input, bit_count))
+        // assert_eq!(expected, part_1_solution(&input, bit_count))
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(matches!(
+            parse("00100\n11110\n1012"),
+            Err(Error::ParseError(ParseError::Located { location, .. })) if location.line == 3
+        ));
+        assert!(matches!(
+            parse("00100\n1111"),
+            Err(Error::ParseError(ParseError::Located { location, .. })) if location.len == 4
+        ));
+        assert!(matches!(
+            parse(""),
+            Err(Error::ParseError(ParseError::UnexpectedEnd))
+        ));
     }
 }
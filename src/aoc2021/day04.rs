@@ -1,7 +1,10 @@
 /// problem: https://adventofcode.com/2021/day/4
 /// input: "https://adventofcode.com/2021/day/4/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 4,
     name: "Giant Squid",
@@ -9,6 +12,7 @@ const P: Problem = Problem {
 
 use std::{collections::HashSet, str::Lines};
 
+#[derive(Clone)]
 pub struct Board {
     rows: Vec<HashSet<usize>>,
     cols: Vec<HashSet<usize>>,
@@ -16,26 +20,33 @@ pub struct Board {
 }
 
 impl Board {
-    pub fn new(lines: &mut Lines) -> Self {
+    /// the next 5 lines of 5 numbers
+    pub fn new(lines: &mut Lines) -> Result<Self, ParseError> {
         let mut rows: Vec<HashSet<usize>> = vec![HashSet::new(); 5];
         let mut cols: Vec<HashSet<usize>> = vec![HashSet::new(); 5];
-        for (row, line) in lines.take(5).enumerate() {
-            assert!(line != "");
-            for (col, val) in line
+        for row in rows.iter_mut() {
+            let line = lines.next().ok_or(ParseError::UnexpectedEnd)?;
+            let values = line
                 .split_whitespace()
-                .map(|v| v.parse::<usize>().expect("should be number"))
-                .enumerate()
-            {
-                rows.get_mut(row).unwrap().insert(val);
-                cols.get_mut(col).unwrap().insert(val);
+                .map(str::parse::<usize>)
+                .collect::<Result<Vec<usize>, _>>()?;
+            if values.len() != 5 {
+                return Err(ParseError::RaggedRow {
+                    expected: 5,
+                    found: values.len(),
+                });
+            }
+            for (col, val) in cols.iter_mut().zip(values) {
+                row.insert(val);
+                col.insert(val);
             }
         }
 
-        Self {
+        Ok(Self {
             rows,
             cols,
             finished: false,
-        }
+        })
     }
 
     fn process(&mut self, value: usize) -> Option<usize> {
@@ -90,56 +101,78 @@ pub fn part_2_solution(moves: Vec<usize>, boards: Vec<Board>) -> usize {
     last_score
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use anyhow::Result;
-    use std::fs::read_to_string;
-
-    fn read_day4(path: impl AsRef<std::path::Path>) -> Result<(Vec<usize>, Vec<Board>)> {
-        let file = read_to_string(path)?; // need to create this binding as lines takes a reference to a string
-        let mut lines = file.lines();
-        let moves = match lines.next() {
-            Some(s) => s
-                .split(",")
-                .map(|m| m.parse::<usize>().unwrap())
-                .collect::<Vec<usize>>(),
-            None => panic!("moves should be first line"),
-        };
-
-        let mut boards: Vec<Board> = Vec::new();
-        while let Some(empty_line) = lines.next() {
-            assert_eq!(empty_line, "");
-            boards.push(Board::new(&mut lines));
+pub fn parse(input: &str) -> Result<(Vec<usize>, Vec<Board>), ParseError> {
+    let mut lines = input.lines();
+    let moves = lines
+        .next()
+        .ok_or(ParseError::UnexpectedEnd)?
+        .split(",")
+        .map(|m| m.parse::<usize>())
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    let mut boards: Vec<Board> = Vec::new();
+    while let Some(empty_line) = lines.next() {
+        if !empty_line.is_empty() {
+            return Err(ParseError::UnexpectedToken(empty_line.to_owned()));
         }
-        Ok((moves, boards))
+        boards.push(Board::new(&mut lines)?);
     }
+    Ok((moves, boards))
+}
 
-    #[test]
-    fn example_part1() {
-        let (moves, boards) = read_day4(P.example_path("_1")).unwrap();
-        let expected = 4512;
-        assert_eq!(expected, part_1_solution(moves, boards));
+pub struct Day04;
+
+impl Solution for Day04 {
+    const PROBLEM: Problem = P;
+    type Parsed = (Vec<usize>, Vec<Board>);
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        Ok(parse(data)?)
     }
 
-    #[test]
-    fn input_part1() {
-        let (moves, boards) = read_day4(P.input_path()).unwrap();
-        let expected = 8580;
-        assert_eq!(expected, part_1_solution(moves, boards));
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        let (moves, boards) = parsed;
+        part_1_solution(moves.clone(), boards.clone())
     }
 
-    #[test]
-    fn example_part2() {
-        let (moves, boards) = read_day4(P.example_path("_1")).unwrap();
-        let expected = 1924;
-        assert_eq!(expected, part_2_solution(moves, boards));
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        let (moves, boards) = parsed;
+        part_2_solution(moves.clone(), boards.clone())
     }
+}
+
+crate::examples!(Day04, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BOARD: &str =
+        "22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19";
 
     #[test]
-    fn input_part2() {
-        let (moves, boards) = read_day4(P.input_path()).unwrap();
-        let expected = 9576;
-        assert_eq!(expected, part_2_solution(moves, boards));
+    fn malformed() {
+        assert!(parse(&format!("7,4,9\n\n{}", BOARD)).is_ok());
+        assert!(matches!(
+            parse(&format!("7,x,9\n\n{}", BOARD)),
+            Err(ParseError::ParseIntError(_))
+        ));
+        assert!(matches!(
+            parse(&format!("7,4,9\n{}", BOARD)),
+            Err(ParseError::UnexpectedToken(_))
+        ));
+        assert!(matches!(
+            parse(&format!("7,4,9\n\n{}", BOARD.replace(" 19", ""))),
+            Err(ParseError::RaggedRow {
+                expected: 5,
+                found: 4
+            })
+        ));
+        assert!(matches!(
+            parse("7,4,9\n\n22 13 17 11  0"),
+            Err(ParseError::UnexpectedEnd)
+        ));
     }
 }
@@ -1,8 +1,11 @@
-use crate::Problem;
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
 /// problem: https://adventofcode.com/2021/day/5
 /// input: "https://adventofcode.com/2021/day/5/input"
 use std::{cmp::max, str::FromStr};
-const P: Problem = Problem {
+pub const P: Problem = Problem {
     year: 2021,
     day: 5,
     name: "Hydrothermal Venture",
@@ -54,33 +57,33 @@ impl Vent {
 }
 
 impl FromStr for Vent {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start_s, finish_s) = s.split_once(" -> ").expect("should split once at ->");
-        let (start_x, start_y) = start_s.split_once(",").expect("should split once at ,");
-        let start = (
-            start_x.parse::<usize>().expect("should be a number"),
-            start_y.parse::<usize>().expect("should be a number"),
-        );
-
-        let (finish_x, finish_y) = finish_s.split_once(",").expect("should split once at ,");
-        let finish = (
-            finish_x.parse::<usize>().expect("should be a number"),
-            finish_y.parse::<usize>().expect("should be a number"),
-        );
+        let (start_s, finish_s) = s
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::UnexpectedToken(s.to_owned()))?;
+        let start = point(start_s)?;
+        let finish = point(finish_s)?;
         Ok(Self { start, end: finish })
     }
 }
 
-pub fn part_1_solution(vents: Vec<Vent>) -> usize {
-    let mut vents = vents;
-    vents.retain(|vent| vent.is_orthogonal());
+/// an `x,y` end of a vent
+fn point(s: &str) -> Result<(usize, usize), ParseError> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| ParseError::UnexpectedToken(s.to_owned()))?;
+    Ok((x.parse()?, y.parse()?))
+}
+
+pub fn part_1_solution(vents: &[Vent]) -> usize {
+    let vents: Vec<&Vent> = vents.iter().filter(|vent| vent.is_orthogonal()).collect();
     let max_coord = vents
         .iter()
         .map(|vent| max(max(vent.start.0, vent.start.1), max(vent.end.0, vent.end.1)))
         .max()
-        .unwrap();
+        .unwrap_or_default();
 
     let mut seafloor = SeaFloor::new(max_coord + 1);
     for vent in vents {
@@ -96,12 +99,12 @@ pub fn part_1_solution(vents: Vec<Vent>) -> usize {
         .map(|v| if *v > 1 { 1 } else { 0 })
         .sum()
 }
-pub fn part_2_solution(vents: Vec<Vent>) -> usize {
+pub fn part_2_solution(vents: &[Vent]) -> usize {
     let max_coord = vents
         .iter()
         .map(|vent| max(max(vent.start.0, vent.start.1), max(vent.end.0, vent.end.1)))
         .max()
-        .unwrap();
+        .unwrap_or_default();
 
     let mut seafloor = SeaFloor::new(max_coord + 1);
     for vent in vents {
@@ -118,41 +121,56 @@ pub fn part_2_solution(vents: Vec<Vent>) -> usize {
         .sum()
 }
 
-#[cfg(test)]
-mod test {
-    use crate::read_to_one_per_line;
+pub fn parse(input: &str) -> Result<Vec<Vent>, Error> {
+    crate::parse_one_per_line(input)
+}
 
-    use super::*;
+pub struct Day05;
 
-    #[test]
-    fn example_part_1() {
-        let vents = read_to_one_per_line::<Vent>(P.example_path("_1"))
-            .ok()
-            .unwrap();
-        let expected = 5;
-        assert_eq!(expected, part_1_solution(vents));
+impl Solution for Day05 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<Vent>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn input_part_1() {
-        let vents = read_to_one_per_line::<Vent>(P.input_path()).ok().unwrap();
-        let expected = 4421;
-        assert_eq!(expected, part_1_solution(vents));
+    fn pt1(vents: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(vents)
     }
 
-    #[test]
-    fn example_part_2() {
-        let vents = read_to_one_per_line::<Vent>(P.example_path("_1"))
-            .ok()
-            .unwrap();
-        let expected = 12;
-        assert_eq!(expected, part_2_solution(vents));
+    fn pt2(vents: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(vents)
     }
+}
+
+crate::examples!(Day05, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
     #[test]
-    fn input_part_2() {
-        let vents = read_to_one_per_line::<Vent>(P.input_path()).ok().unwrap();
-        let expected = 18674;
-        assert_eq!(expected, part_2_solution(vents));
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(matches!(
+            parse(&input),
+            Err(Error::ParseError(ParseError::Located { location, .. })) if location.line == 2
+        ));
+        let mut lines = input.lines().skip(1).map(Vent::from_str);
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedToken(l))) if l == "0,9 => 5,9"
+        ));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedToken(p))) if p == "9"
+        ));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::ParseIntError(_)))
+        ));
     }
 }
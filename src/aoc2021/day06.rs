@@ -1,101 +1,84 @@
 /// problem: https://adventofcode.com/2021/day/6
 /// input: "https://adventofcode.com/2021/day/6/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    simulate, Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 6,
     name: "Lanternfish",
 };
 
-use std::collections::VecDeque;
-
-pub fn part_1_solution(initial_state: Vec<usize>) -> usize {
-    let mut fish_counts = VecDeque::from([0; 9]); // [0, 0, 0, 0, 0, 0, 0, 0, 0]
+fn count_fish(initial_state: &[usize], days: usize) -> usize {
+    let mut fish_counts = [0; 9]; // fish_counts[age] is how many fish are that age
 
     // for the fish ages in the initial state, increment the fishcount
     // initial_state = [3, 4, 3, 1, 2]
     // fish_counts = [0, 1, 1, 2, 1, 0, 0, 0, 0]
-    for fish_age in initial_state {
+    for &fish_age in initial_state {
         fish_counts[fish_age] += 1;
     }
-    let mut baby_fish;
-    for _ in 0..80 {
-        baby_fish = fish_counts.pop_front().unwrap();
-        fish_counts.push_back(baby_fish); // baby fish
-        fish_counts[6] += baby_fish; // adults reset to day 6
-    }
+    let fish_counts = simulate::run(fish_counts, days, |fish_counts| {
+        fish_counts.rotate_left(1); // the age 0 fish spawn baby fish at age 8
+        fish_counts[6] += fish_counts[8]; // adults reset to day 6
+    });
     fish_counts.iter().sum()
 }
 
-pub fn part_2_solution(initial_state: Vec<usize>) -> usize {
-    let mut fish_counts = VecDeque::from([0; 9]);
+pub fn part_1_solution(initial_state: &[usize]) -> usize {
+    count_fish(initial_state, 80)
+}
 
-    for fish_age in initial_state {
-        fish_counts[fish_age] += 1;
-    }
-    let mut baby_fish;
-    for _ in 0..256 {
-        baby_fish = fish_counts.pop_front().unwrap();
-        fish_counts.push_back(baby_fish);
-        fish_counts[6] += baby_fish;
-    }
-    fish_counts.iter().sum()
+pub fn part_2_solution(initial_state: &[usize]) -> usize {
+    count_fish(initial_state, 256)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::fs::read_to_string;
+/// the age of each fish, from 0 to 8
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    input
+        .trim() // get rid of trailing blank line
+        .split(',')
+        .map(|v| match v.parse::<usize>()? {
+            age @ 0..=8 => Ok(age),
+            _ => Err(ParseError::UnexpectedToken(v.to_owned())),
+        })
+        .collect()
+}
 
-    #[test]
-    fn example_part_1() {
-        let initial_state = read_to_string(P.example_path("_1"))
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .split(",")
-            .map(|v| v.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
+pub struct Day06;
+
+impl Solution for Day06 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<usize>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-        let expected = 5934;
-        assert_eq!(expected, part_1_solution(initial_state));
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        Ok(parse(data)?)
     }
 
-    #[test]
-    fn input_part_1() {
-        let initial_state = read_to_string(P.input_path())
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .split(",")
-            .map(|v| v.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
+    fn pt1(initial_state: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(initial_state)
+    }
 
-        let expected = 386640;
-        assert_eq!(expected, part_1_solution(initial_state));
+    fn pt2(initial_state: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(initial_state)
     }
+}
 
-    #[test]
-    fn example_part_2() {
-        let initial_state = read_to_string(P.example_path("_1"))
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .split(",")
-            .map(|v| v.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
+crate::examples!(Day06, pt1: [_1], pt2: [_1]);
 
-        let expected = 26984457539;
-        assert_eq!(expected, part_2_solution(initial_state));
-    }
+#[cfg(test)]
+mod test {
+    use super::*;
 
     #[test]
-    fn input_part_2() {
-        let initial_state = read_to_string(P.input_path())
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .split(",")
-            .map(|v| v.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
-
-        let expected = 1733403626279;
-        assert_eq!(expected, part_2_solution(initial_state));
+    fn malformed() {
+        assert!(matches!(parse("3,4,x"), Err(ParseError::ParseIntError(_))));
+        assert!(matches!(
+            parse("3,4,9"),
+            Err(ParseError::UnexpectedToken(age)) if age == "9"
+        ));
     }
 }
@@ -1,119 +1,78 @@
 /// problem: https://adventofcode.com/2021/day/7
 /// input: "https://adventofcode.com/2021/day/7/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    math, Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 7,
     name: "The Treachery of Whales",
 };
 
-fn triangle_number(n: usize) -> usize {
-    n * (n + 1) / 2
-}
-
-fn reverse_triangle_number(n: usize) -> usize {
-    ((2 * n) as f32).sqrt() as usize
-}
-
-fn median<T: Ord + Clone>(v: &[T]) -> T {
-    let mut vs = v.to_owned();
-    vs.sort();
-    vs[vs.len() / 2].to_owned()
-}
-
-pub fn part_1_solution(crab_positions: Vec<usize>) -> usize {
-    let pivot = median(&crab_positions);
+pub fn part_1_solution(crab_positions: &[usize]) -> usize {
+    let pivot = math::median(crab_positions).unwrap();
     crab_positions
         .iter()
         .map(|c| c.abs_diff(pivot))
         .sum::<usize>()
 }
 
-pub fn part_2_solution_attempt(crab_positions: Vec<usize>) -> usize {
-    // let mut pivot = median(&crab_positions);
-    // pivot = pivot + triangle_number(pivot);
-    let pivot = reverse_triangle_number(median(
-        &crab_positions
-            .iter()
-            .map(|n| triangle_number(*n))
-            .collect::<Vec<usize>>(),
-    ));
-    println!("{}", pivot);
-    crab_positions
-        .iter()
-        .map(|c| triangle_number(c.abs_diff(pivot)))
-        .sum::<usize>()
-}
-
-pub fn part_2_solution(crab_positions: Vec<usize>) -> usize {
-    // let mut pivot = median(&crab_positions);
-    // pivot = pivot + triangle_number(pivot);
-    (0..(*crab_positions.iter().max().unwrap()))
+pub fn part_2_solution(crab_positions: &[usize]) -> usize {
+    // the best pivot is within half a step of the mean
+    let mean = math::mean(crab_positions).unwrap();
+    [mean.floor() as usize, mean.ceil() as usize]
+        .into_iter()
         .map(|p| {
             crab_positions
                 .iter()
-                .map(|&c| triangle_number(c.abs_diff(p)))
+                .map(|&c| math::triangle(c.abs_diff(p) as u64) as usize)
                 .sum::<usize>()
         })
         .min()
         .unwrap()
 }
 
-#[cfg(test)]
-mod test {
-    use std::fs::read_to_string;
+/// at least one position, since an empty input is a single unparseable ""
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    input
+        .trim() // get rid of trailing blank line
+        .split(',')
+        .map(|v| v.parse::<usize>())
+        .collect::<Result<_, _>>()
+        .map_err(ParseError::from)
+}
 
-    use super::*;
+pub struct Day07;
 
-    #[test]
-    fn example_part_1() {
-        let crab_positions = read_to_string(P.example_path("_1"))
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .split(",")
-            .map(|v| v.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
+impl Solution for Day07 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<usize>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-        let expected = 37;
-        assert_eq!(expected, part_1_solution(crab_positions));
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        Ok(parse(data)?)
     }
 
-    #[test]
-    fn input_part_1() {
-        let crab_positions = read_to_string(P.input_path())
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .split(",")
-            .map(|v| v.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
+    fn pt1(crab_positions: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(crab_positions)
+    }
 
-        let expected = 353800;
-        assert_eq!(expected, part_1_solution(crab_positions));
+    fn pt2(crab_positions: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(crab_positions)
     }
+}
 
-    #[test]
-    fn example_part_2() {
-        let crab_positions = read_to_string(P.example_path("_1"))
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .split(",")
-            .map(|v| v.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
+crate::examples!(Day07, pt1: [_1], pt2: [_1]);
 
-        let expected = 168;
-        assert_eq!(expected, part_2_solution(crab_positions));
-    }
+#[cfg(test)]
+mod test {
+    use super::*;
 
     #[test]
-    fn input_part_2() {
-        let crab_positions = read_to_string(P.input_path())
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .split(",")
-            .map(|v| v.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
-
-        let expected = 98119739;
-        assert_eq!(expected, part_2_solution(crab_positions));
+    fn malformed() {
+        assert!(matches!(parse("16,1,x"), Err(ParseError::ParseIntError(_))));
+        assert!(matches!(parse("\n"), Err(ParseError::ParseIntError(_))));
     }
 }
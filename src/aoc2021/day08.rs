@@ -1,7 +1,10 @@
 /// problem: https://adventofcode.com/2021/day/8
 /// input: "https://adventofcode.com/2021/day/8/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 8,
     name: "Seven Segment Search",
@@ -40,28 +43,36 @@ pub struct SegmentBits {
 }
 
 impl FromStr for SegmentBits {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (left, right) = s.split_once('|').expect("should have |");
+        let (left, right) = split_bar(s)?;
         let input: Vec<u8> = left
             .split_whitespace()
-            .map(|s| {
-                s.bytes()
-                    .fold(0, |state, c| state ^ (1 << (c - b'a') as u8))
-            })
-            .collect();
+            .map(segment_bits)
+            .collect::<Result<_, _>>()?;
         let output: Vec<u8> = right
             .split_whitespace()
-            .map(|s| {
-                s.chars()
-                    .fold(0, |state, c| state ^ (1 << (c as u8 - b'a') as u8))
-            })
-            .collect();
+            .map(segment_bits)
+            .collect::<Result<_, _>>()?;
         Ok(Self { input, output })
     }
 }
 
+/// the segments lit in a pattern, bit 0 for 'a' up to bit 6 for 'g'
+fn segment_bits(s: &str) -> Result<u8, ParseError> {
+    s.chars().try_fold(0, |state, c| match c {
+        'a'..='g' => Ok(state ^ (1 << (c as u8 - b'a'))),
+        c => Err(ParseError::UnexpectedChar(c)),
+    })
+}
+
+/// the signal patterns and the output value either side of the '|'
+fn split_bar(s: &str) -> Result<(&str, &str), ParseError> {
+    s.split_once('|')
+        .ok_or_else(|| ParseError::UnexpectedToken(s.to_owned()))
+}
+
 impl SegmentBits {
     fn seg_len(&self, index: usize) -> usize {
         self.input.get(index).expect("out of bounds").count_ones() as usize
@@ -164,11 +175,11 @@ pub struct Segment {
 }
 
 impl FromStr for Segment {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // "split at |"
-        let (left, right) = s.split_once('|').expect("should have |");
+        let (left, right) = split_bar(s)?;
         let input: Vec<String> = left.split_whitespace().map(|s| s.to_owned()).collect();
         let output: Vec<String> = right.split_whitespace().map(|s| s.to_owned()).collect();
         Ok(Self { input, output })
@@ -184,53 +195,70 @@ impl Segment {
     }
 }
 
-pub fn part_1_solution(segments: Vec<Segment>) -> usize {
+pub fn part_1_solution(segments: &[Segment]) -> usize {
     segments
         .iter()
         .map(|segment| segment.count_1478())
         .sum::<usize>()
 }
 
-pub fn part_2_solution(segments: Vec<SegmentBits>) -> usize {
+pub fn part_2_solution(segments: &[SegmentBits]) -> usize {
     segments
         .iter()
         .map(|segment| segment.sum_output())
         .sum::<usize>()
 }
 
-#[cfg(test)]
-mod test {
-    use crate::read_to_one_per_line;
+pub fn parse<T: FromStr<Err = ParseError>>(input: &str) -> Result<Vec<T>, Error> {
+    crate::parse_one_per_line(input)
+}
 
-    use super::*;
+pub struct Day08;
 
-    #[test]
-    fn example_part_1() {
-        let segments = read_to_one_per_line::<Segment>(P.example_path("_1")).unwrap();
-        let expected = 26;
-        assert_eq!(expected, part_1_solution(segments));
+impl Solution for Day08 {
+    const PROBLEM: Problem = P;
+    type Parsed = (Vec<Segment>, Vec<SegmentBits>);
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        Ok((parse(data)?, parse(data)?))
     }
 
-    #[test]
-    fn input_part_1() {
-        let segments = read_to_one_per_line::<Segment>(P.input_path()).unwrap();
-        let expected = 381;
-        assert_eq!(expected, part_1_solution(segments));
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(&parsed.0)
     }
 
-    #[test]
-    fn example_part_2() {
-        let segments = read_to_one_per_line::<SegmentBits>(P.example_path("_1")).unwrap();
-        println!("{:?}", segments[0]);
-        let expected = 61229;
-        assert_eq!(expected, part_2_solution(segments));
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(&parsed.1)
     }
+}
+
+crate::examples!(Day08, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
     #[test]
-    fn input_part_2() {
-        let segments = read_to_one_per_line::<SegmentBits>(P.input_path()).unwrap();
-        println!("{:?}", segments[0]);
-        let expected = 1023686;
-        assert_eq!(expected, part_2_solution(segments));
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(matches!(
+            Day08::parse(&input),
+            Err(Error::ParseError(ParseError::Located { location, .. })) if location.line == 1
+        ));
+        let mut lines = input.lines().map(SegmentBits::from_str);
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedToken(_)))
+        ));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedChar('h')))
+        ));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedChar('A')))
+        ));
     }
 }
@@ -1,128 +1,53 @@
 /// problem: https://adventofcode.com/2021/day/9
 /// input: "https://adventofcode.com/2021/day/9/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{error::Error, Problem, Solution};
+pub const P: Problem = Problem {
     year: 2021,
     day: 9,
     name: "Smoke Basin",
 };
-use std::{collections::VecDeque, usize::MAX};
-
-struct Neighbours(Vec<(usize, usize)>);
+use crate::{
+    grid::{Grid, Pos},
+    image::{Export, Rgb, DISTINCT, WHITE},
+    search::reachable,
+};
+use std::collections::HashSet;
 
-impl Neighbours {
-    fn from_node(node: &(usize, usize)) -> Self {
-        let (x, y) = (node.0.to_owned(), node.1.to_owned()); // does this copy or clone?
-        let mut neighbours = vec![(x + 1, y), (x, y + 1)];
-        if x != 0 {
-            neighbours.push((x - 1, y));
-        }
-        if y != 0 {
-            neighbours.push((x, y - 1));
-        }
-        Self(neighbours)
+/// marks every cell in the basin around `start` as visited and returns them
+fn basin(cave_depths: &Grid<usize>, visited: &mut Grid<bool>, start: Pos) -> HashSet<Pos> {
+    if cave_depths[start] == 9 {
+        visited[start] = true;
+        return HashSet::new();
     }
-}
-
-impl IntoIterator for Neighbours {
-    type Item = (usize, usize);
-
-    type IntoIter = std::vec::IntoIter<Self::Item>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+    let in_basin = |&node: &Pos| {
+        cave_depths
+            .neighbours4(node)
+            .filter(|&n| cave_depths[n] != 9)
+    };
+    let basin = reachable([start], in_basin);
+    for &node in &basin {
+        visited[node] = true;
     }
+    basin
 }
 
-fn bfs(
-    cave_depths: &Vec<Vec<usize>>,
-    visited: &mut Vec<Vec<bool>>,
-    start: (usize, usize),
-) -> usize {
-    visited[start.0][start.1] = true;
-    if cave_depths[start.0][start.1] == 9 {
-        return 0;
-    }
-
-    let mut basin_nodes = VecDeque::from([start]);
-    let mut basin_size = 0;
-    while let Some(next_node) = basin_nodes.pop_front() {
-        basin_size += 1;
-        for neighbour in Neighbours::from_node(&next_node) {
-            if let Some(row) = visited.get_mut(neighbour.0) {
-                if let Some(node_is_visited) = row.get_mut(neighbour.1) {
-                    if !*node_is_visited {
-                        *node_is_visited = true; // could use indexing as well
-                        if cave_depths[neighbour.0][neighbour.1] != 9 {
-                            // bounds checking is done here
-                            basin_nodes.push_back(neighbour);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    basin_size
+/// the points lower than each of their neighbours
+fn low_points(cave_depths: &Grid<usize>) -> impl Iterator<Item = (Pos, &usize)> {
+    cave_depths
+        .iter()
+        .filter(|&(pos, curr)| cave_depths.neighbours4(pos).all(|n| *curr < cave_depths[n]))
 }
 
-// fn pad_vec(v: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
-//     let mut v = v;
-//     let push_val = 9;
-
-//     for row in v.iter_mut() {
-//         row.push(push_val);
-//         row.insert(0, push_val)
-//     }
-//     v.push(vec![push_val; v[0].len()]);
-//     v.insert(0, vec![push_val; v[0].len()]);
-//     v
-// }
-
-pub fn part_1_solution(cave_depths: Vec<Vec<usize>>) -> usize {
-    let mut lowest = 0;
-    let max_row_index = cave_depths.len() - 1;
-    let max_col_index = cave_depths[0].len() - 1;
-
-    for row in 0..cave_depths.len() {
-        for col in 0..cave_depths[0].len() {
-            let curr = cave_depths[row][col];
-            let left = if col == 0 {
-                MAX
-            } else {
-                cave_depths[row][col - 1]
-            };
-            let up = if row == 0 {
-                MAX
-            } else {
-                cave_depths[row - 1][col]
-            };
-            let right = if col == max_col_index {
-                MAX
-            } else {
-                cave_depths[row][col + 1]
-            };
-            let down = if row == max_row_index {
-                MAX
-            } else {
-                cave_depths[row + 1][col]
-            };
-
-            if curr < left && curr < up && curr < right && curr < down {
-                lowest += curr + 1;
-            }
-        }
-    }
-    lowest
+pub fn part_1_solution(cave_depths: &Grid<usize>) -> usize {
+    low_points(cave_depths).map(|(_, curr)| curr + 1).sum()
 }
 
-pub fn part_2_solution(cave_depths: Vec<Vec<usize>>) -> usize {
-    let mut visited = vec![vec![false; cave_depths[0].len()]; cave_depths.len()];
+pub fn part_2_solution(cave_depths: &Grid<usize>) -> usize {
+    let mut visited = cave_depths.map(|_| false);
     let mut scores: Vec<usize> = Vec::new();
-    for row in 0..cave_depths.len() {
-        for col in 0..cave_depths[0].len() {
-            if !visited[row][col] {
-                scores.push(bfs(&cave_depths, &mut visited, (row, col)));
-            }
+    for pos in cave_depths.positions() {
+        if !visited[pos] {
+            scores.push(basin(cave_depths, &mut visited, pos).len());
         }
     }
     scores.sort();
@@ -130,47 +55,55 @@ pub fn part_2_solution(cave_depths: Vec<Vec<usize>>) -> usize {
     scores.pop().unwrap() * scores.pop().unwrap() * scores.pop().unwrap()
 }
 
-#[cfg(test)]
-mod test {
-    use crate::read_to_vec_per_line;
+pub fn parse(input: &str) -> Result<Grid<usize>, Error> {
+    Grid::parse_with(input, |c| c.to_digit(10).map(|d| d as usize))
+}
+
+pub struct Day09;
 
-    use super::*;
+impl Solution for Day09 {
+    const PROBLEM: Problem = P;
+    type Parsed = Grid<usize>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    #[test]
-    fn example_part_1() {
-        let cave_depths = read_to_vec_per_line(P.example_path("_1"), |c| {
-            Some(c.to_digit(10).unwrap() as usize)
-        })
-        .unwrap();
-        let expected = 15;
-        assert_eq!(expected, part_1_solution(cave_depths))
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn input_part_1() {
-        let cave_depths =
-            read_to_vec_per_line(P.input_path(), |c| Some(c.to_digit(10).unwrap() as usize))
-                .unwrap();
-        let expected = 560;
-        assert_eq!(expected, part_1_solution(cave_depths))
+    fn pt1(cave_depths: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(cave_depths)
     }
 
-    #[test]
-    fn example_part_2() {
-        let cave_depths = read_to_vec_per_line(P.example_path("_1"), |c| {
-            Some(c.to_digit(10).unwrap() as usize)
-        })
-        .unwrap();
-        let expected = 1134;
-        assert_eq!(expected, part_2_solution(cave_depths))
+    fn pt2(cave_depths: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(cave_depths)
     }
+}
 
-    #[test]
-    fn input_part_2() {
-        let cave_depths =
-            read_to_vec_per_line(P.input_path(), |c| Some(c.to_digit(10).unwrap() as usize))
-                .unwrap();
-        let expected = 959136;
-        assert_eq!(expected, part_2_solution(cave_depths))
+impl Export for Day09 {
+    /// each basin in its own colour, with the ridges of 9s dark and the low points white
+    fn export(cave_depths: &Self::Parsed, emit: &mut dyn FnMut(Grid<Rgb>)) {
+        let mut image = cave_depths.map(|_| [30, 30, 30]);
+        let mut visited = cave_depths.map(|_| false);
+        let mut basins = 0;
+        for pos in cave_depths.positions() {
+            if visited[pos] {
+                continue;
+            }
+            let cells = basin(cave_depths, &mut visited, pos);
+            if cells.is_empty() {
+                continue;
+            }
+            for cell in cells {
+                image[cell] = DISTINCT[basins % DISTINCT.len()];
+            }
+            basins += 1;
+        }
+        for (pos, _) in low_points(cave_depths) {
+            image[pos] = WHITE;
+        }
+        emit(image);
     }
 }
+
+crate::examples!(Day09, pt1: [_1], pt2: [_1]);
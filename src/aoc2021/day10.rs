@@ -1,7 +1,10 @@
 /// problem: https://adventofcode.com/2021/day/10
 /// input: "https://adventofcode.com/2021/day/10/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 10,
     name: "Syntax Scoring",
@@ -82,7 +85,7 @@ fn get_missing_tokens(chunk: &str) -> Option<String> {
     }
 }
 
-pub fn part_1_solution(chunks: Vec<String>) -> usize {
+pub fn part_1_solution(chunks: &[String]) -> usize {
     let points = HashMap::from([(')', 3), (']', 57), ('}', 1197), ('>', 25137)]);
 
     chunks
@@ -91,64 +94,101 @@ pub fn part_1_solution(chunks: Vec<String>) -> usize {
         .sum()
 }
 
-pub fn part_2_solution(chunks: Vec<String>) -> usize {
+pub fn part_2_solution(chunks: &[String]) -> usize {
     let points = HashMap::from([(')', 1), (']', 2), ('}', 3), ('>', 4)]);
 
     let non_corrupted_chunks = chunks
         .iter()
-        .filter(|chunk| match get_corrupted_token(chunk) {
-            None => true,
-            _ => false,
-        });
+        .filter(|chunk| get_corrupted_token(chunk).is_none());
 
     let mut scores: Vec<usize> = non_corrupted_chunks
         .filter_map(|chunk| {
-            if let Some(missing_tokens) = get_missing_tokens(&chunk) {
-                Some(
-                    missing_tokens
-                        .chars()
-                        .fold(0, |acc, token| acc * 5 + points[&token]), // calculate the scores
-                )
-            } else {
-                None
-            }
+            get_missing_tokens(chunk).map(|missing_tokens| {
+                missing_tokens
+                    .chars()
+                    .fold(0, |acc, token| acc * 5 + points[&token]) // calculate the scores
+            })
         })
         .collect();
     scores.sort();
-    scores[scores.len() / 2]
+    scores.get(scores.len() / 2).copied().unwrap_or_default()
 }
 
-#[cfg(test)]
-mod test {
-    use crate::read_to_one_per_line;
+/// where a chunk has a char that isn't a bracket, or closes more than it opened,
+/// up to where it's corrupted
+fn invalid_token(chunk: &str) -> Option<(usize, char)> {
+    let mut token_stack = Vec::new();
+    for (col, token) in chunk.chars().enumerate() {
+        if let Some(i) = OPENNERS.iter().position(|&o| o == token) {
+            token_stack.push(i);
+        } else if let Some(i) = CLOSERS.iter().position(|&c| c == token) {
+            match token_stack.pop() {
+                Some(opened) if opened == i => {}
+                Some(_) => return None, // corrupted, which part 1 scores
+                None => return Some((col, token)),
+            }
+        } else {
+            return Some((col, token));
+        }
+    }
+    None
+}
 
-    use super::*;
+/// one chunk per line, made of brackets that never close more than they open
+pub fn parse(input: &str) -> Result<Vec<String>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match invalid_token(line) {
+            None => Ok(line.to_owned()),
+            Some((col, token)) => {
+                Err(
+                    ParseError::located(i + 1, col + 1, 1, line, ParseError::UnexpectedChar(token))
+                        .into(),
+                )
+            }
+        })
+        .collect()
+}
 
-    #[test]
-    fn example_part_1() {
-        let chunks = read_to_one_per_line::<String>(P.example_path("_1")).unwrap();
-        let expected = 26397;
-        assert_eq!(expected, part_1_solution(chunks))
+pub struct Day10;
+
+impl Solution for Day10 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<String>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn input_part_1() {
-        let chunks = read_to_one_per_line::<String>(P.input_path()).unwrap();
-        let expected = 296535;
-        assert_eq!(expected, part_1_solution(chunks))
+    fn pt1(chunks: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(chunks)
     }
 
-    #[test]
-    fn example_part_2() {
-        let chunks = read_to_one_per_line::<String>(P.example_path("_1")).unwrap();
-        let expected = 288957;
-        assert_eq!(expected, part_2_solution(chunks))
+    fn pt2(chunks: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(chunks)
     }
+}
+
+crate::examples!(Day10, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
     #[test]
-    fn input_part_2() {
-        let chunks = read_to_one_per_line::<String>(P.input_path()).unwrap();
-        let expected = 4245130838;
-        assert_eq!(expected, part_2_solution(chunks))
+    fn malformed() {
+        assert!(parse("[({(<(())[]>[[{[]{<()<>>\n(])").is_ok());
+        assert!(matches!(
+            parse("[]\n(())x"),
+            Err(Error::ParseError(ParseError::Located { location, .. }))
+                if (location.line, location.col) == (2, 5)
+        ));
+        assert!(matches!(
+            parse("())"),
+            Err(Error::ParseError(ParseError::Located { location, .. })) if location.col == 3
+        ));
     }
 }
@@ -1,161 +1,135 @@
 /// problem: https://adventofcode.com/2021/day/11
 /// input: "https://adventofcode.com/2021/day/11/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::Error,
+    simulate,
+    visualize::{Cell, Color, Frame, Visualize},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 11,
     name: "Dumbo Octopus",
 };
+use crate::grid::{Grid, Pos};
 use std::collections::HashSet;
 
-fn octopus_neighbours(octopus: &(usize, usize)) -> Vec<(usize, usize)> {
-    let mut neighbours = Vec::new();
-    let (x, y) = *octopus;
-    if x > 0 {
-        neighbours.push((x - 1, y));
-        if y > 0 {
-            neighbours.push((x - 1, y - 1));
-        }
-        if y < 9 {
-            neighbours.push((x - 1, y + 1));
-        }
-    }
-    if x < 9 {
-        neighbours.push((x + 1, y));
-        if y > 0 {
-            neighbours.push((x + 1, y - 1));
-        }
-        if y < 9 {
-            neighbours.push((x + 1, y + 1));
-        }
-    }
-    if y > 0 {
-        neighbours.push((x, y - 1));
-    }
-    if y < 9 {
-        neighbours.push((x, y + 1));
-    }
-    neighbours
-}
-
-fn flash(
-    octopii_state: &mut Vec<Vec<usize>>,
-    flashed: &mut HashSet<(usize, usize)>,
-    flashing_octopus: &(usize, usize),
-) {
+fn flash(octopii_state: &mut Grid<usize>, flashed: &mut HashSet<Pos>, flashing_octopus: Pos) {
     // recursive
-    let neighbours = octopus_neighbours(&flashing_octopus);
+    let neighbours: Vec<Pos> = octopii_state.neighbours8(flashing_octopus).collect();
 
-    for (x, y) in neighbours {
-        if flashed.contains(&(x, y)) {
+    for octopus in neighbours {
+        if flashed.contains(&octopus) {
             // already flashed
             continue;
         }
-        octopii_state[x][y] += 1;
-        if octopii_state[x][y] > 9 {
-            flashed.insert((x, y));
-            flash(octopii_state, flashed, &(x, y));
+        octopii_state[octopus] += 1;
+        if octopii_state[octopus] > 9 {
+            flashed.insert(octopus);
+            flash(octopii_state, flashed, octopus);
         }
     }
 }
 
-fn process_octopii(octopii_state: &mut Vec<Vec<usize>>) -> usize {
-    let mut flashed: HashSet<(usize, usize)> = HashSet::new();
+fn process_octopii(octopii_state: &mut Grid<usize>) -> usize {
+    let mut flashed: HashSet<Pos> = HashSet::new();
 
     // increment all the octopii and locate the flashing ones
-    for (i, row) in octopii_state.iter_mut().enumerate() {
-        for (j, state) in row.iter_mut().enumerate() {
-            if *state == 9 {
-                flashed.insert((i, j));
-            }
-            *state += 1;
+    for octopus in octopii_state.positions() {
+        if octopii_state[octopus] == 9 {
+            flashed.insert(octopus);
         }
+        octopii_state[octopus] += 1;
     }
 
     // for the original flashing octopii, instruct them to flash.
     // the secondary flashing octopii will be added to this set AFTER they flash
-    for flashing_octopus in flashed.clone().iter() {
+    for flashing_octopus in flashed.clone() {
         flash(octopii_state, &mut flashed, flashing_octopus);
     }
 
     // reset the flashed octopii
     for flashed_octopus in flashed.iter() {
-        let (x, y) = *flashed_octopus;
-        octopii_state[x][y] = 0;
+        octopii_state[*flashed_octopus] = 0;
     }
     flashed.len()
 }
 
-pub fn part_1_solution(octopii_state: Vec<Vec<usize>>) -> usize {
-    let mut octopii_state = octopii_state;
+pub fn part_1_solution(octopii_state: Grid<usize>) -> usize {
     let mut octopii_flashes = 0;
-    for _day in 0..100 {
-        octopii_flashes += process_octopii(&mut octopii_state);
-    }
+    simulate::run(octopii_state, 100, |octopii_state| {
+        octopii_flashes += process_octopii(octopii_state);
+    });
     octopii_flashes
 }
 
-pub fn part_2_solution(octopii_state: Vec<Vec<usize>>) -> usize {
+pub fn part_2_solution(octopii_state: Grid<usize>) -> usize {
+    // every octopus flashed on the day they are all back to 0
     let mut octopii_state = octopii_state;
-    for day in 1.. {
-        if process_octopii(&mut octopii_state) == 100 {
-            return day;
-        };
-    }
-    0
+    simulate::run_until(
+        &mut octopii_state,
+        |octopii_state| {
+            process_octopii(octopii_state);
+        },
+        |octopii_state| octopii_state.values().all(|&o| o == 0),
+    )
+}
+
+pub fn parse(input: &str) -> Result<Grid<usize>, Error> {
+    Grid::parse_with(input, |c| c.to_digit(10).map(|d| d as usize))
 }
 
-#[cfg(test)]
-mod test {
-    use crate::read_to_vec_per_line;
+pub struct Day11;
 
-    use super::*;
+impl Solution for Day11 {
+    const PROBLEM: Problem = P;
+    type Parsed = Grid<usize>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    #[test]
-    fn example_part_1() {
-        let octopii: Vec<Vec<usize>> = read_to_vec_per_line(P.example_path("_1"), |c| {
-            Some(c.to_digit(10).unwrap() as usize)
-        })
-        .unwrap();
-        let expected = 1656;
-        assert_eq!(expected, part_1_solution(octopii))
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn example2_part_1() {
-        let octopii: Vec<Vec<usize>> = read_to_vec_per_line(P.example_path("_2"), |c| {
-            Some(c.to_digit(10).unwrap() as usize)
-        })
-        .unwrap();
-        let expected = 1004;
-        assert_eq!(expected, part_1_solution(octopii))
+    fn pt1(octopii: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(octopii.clone())
     }
 
-    #[test]
-    fn input_part_1() {
-        let octopii: Vec<Vec<usize>> =
-            read_to_vec_per_line(P.input_path(), |c| Some(c.to_digit(10).unwrap() as usize))
-                .unwrap();
-        let expected = 1659;
-        assert_eq!(expected, part_1_solution(octopii))
+    fn pt2(octopii: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(octopii.clone())
     }
+}
 
-    #[test]
-    fn example_part_2() {
-        let octopii: Vec<Vec<usize>> = read_to_vec_per_line(P.example_path("_1"), |c| {
-            Some(c.to_digit(10).unwrap() as usize)
-        })
-        .unwrap();
-        let expected = 195;
-        assert_eq!(expected, part_2_solution(octopii))
-    }
+impl Visualize for Day11 {
+    /// part 2, a frame per step until every octopus flashes at once
+    fn visualize(octopii: &Self::Parsed, emit: &mut dyn FnMut(Frame)) {
+        let draw = |octopii: &Grid<usize>, caption: String| {
+            Frame::from_fn(caption, octopii.width(), octopii.height(), |pos| {
+                let c = char::from_digit(octopii[pos] as u32, 10).unwrap();
+                match octopii[pos] {
+                    0 => Cell::new(c, Color::Yellow),
+                    9 => Cell::new(c, Color::White),
+                    _ => Cell::new(c, Color::Blue),
+                }
+            })
+        };
 
-    #[test]
-    fn input_part_2() {
-        let octopii: Vec<Vec<usize>> =
-            read_to_vec_per_line(P.input_path(), |c| Some(c.to_digit(10).unwrap() as usize))
-                .unwrap();
-        let expected = 227;
-        assert_eq!(expected, part_2_solution(octopii))
+        let mut octopii = octopii.clone();
+        let (mut steps, mut flashes) = (0, 0);
+        emit(draw(&octopii, "step 0".to_owned()));
+        simulate::run_until(
+            &mut octopii,
+            |octopii| {
+                steps += 1;
+                flashes += process_octopii(octopii);
+                emit(draw(
+                    octopii,
+                    format!("step {}, {} flashes", steps, flashes),
+                ));
+            },
+            |octopii| octopii.values().all(|&o| o == 0),
+        );
     }
 }
+
+crate::examples!(Day11, pt1: [_1, _2], pt2: [_1]);
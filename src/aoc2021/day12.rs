@@ -1,7 +1,7 @@
 /// problem: https://adventofcode.com/2021/day/12
 /// input: "https://adventofcode.com/2021/day/12/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{error::Error, Problem, Solution};
+pub const P: Problem = Problem {
     year: 2021,
     day: 12,
     name: "Passage Pathing",
@@ -55,7 +55,7 @@ impl FromStr for Edge {
     }
 }
 
-pub fn part_1_solution(caves: HashMap<String, Cave>) -> usize {
+pub fn part_1_solution(caves: &HashMap<String, Cave>) -> usize {
     // all paths
     // tedious
     let mut complete_paths: HashSet<Vec<String>> = HashSet::new();
@@ -86,7 +86,7 @@ struct Path {
     has_visited_small_twice: bool,
 }
 
-pub fn part_2_solution(caves: HashMap<String, Cave>) -> usize {
+pub fn part_2_solution(caves: &HashMap<String, Cave>) -> usize {
     // all paths
     // tedious
     let mut complete_paths: HashSet<Vec<String>> = HashSet::new();
@@ -126,59 +126,33 @@ pub fn part_2_solution(caves: HashMap<String, Cave>) -> usize {
     complete_paths.len()
 }
 
-#[cfg(test)]
-mod test {
-    use std::collections::HashMap;
-
-    use crate::read_to_one_per_line;
-
-    use super::*;
-
-    #[test]
-    fn example_part_1() {
-        let edges: Vec<Edge> = read_to_one_per_line::<Edge>(P.example_path("_1")).unwrap();
-        let mut caves: HashMap<String, Cave> = HashMap::new();
-        for edge in edges {
-            add_edge_to_caves(&mut caves, edge);
-        }
-
-        let expected = 10;
-        assert_eq!(expected, part_1_solution(caves))
+pub fn parse(input: &str) -> Result<HashMap<String, Cave>, Error> {
+    let mut caves: HashMap<String, Cave> = HashMap::new();
+    for line in input.lines() {
+        add_edge_to_caves(&mut caves, line.parse::<Edge>()?);
     }
+    Ok(caves)
+}
 
-    #[test]
-    fn input_part_1() {
-        let edges: Vec<Edge> = read_to_one_per_line::<Edge>(P.input_path()).unwrap();
-        let mut caves: HashMap<String, Cave> = HashMap::new();
-        for edge in edges {
-            add_edge_to_caves(&mut caves, edge);
-        }
-
-        let expected = 3679;
-        assert_eq!(expected, part_1_solution(caves))
-    }
+pub struct Day12;
 
-    #[test]
-    fn example_part_2() {
-        let edges: Vec<Edge> = read_to_one_per_line::<Edge>(P.example_path("_1")).unwrap();
-        let mut caves: HashMap<String, Cave> = HashMap::new();
-        for edge in edges {
-            add_edge_to_caves(&mut caves, edge);
-        }
+impl Solution for Day12 {
+    const PROBLEM: Problem = P;
+    type Parsed = HashMap<String, Cave>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-        let expected = 36;
-        assert_eq!(expected, part_2_solution(caves))
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn input_part_2() {
-        let edges: Vec<Edge> = read_to_one_per_line::<Edge>(P.input_path()).unwrap();
-        let mut caves: HashMap<String, Cave> = HashMap::new();
-        for edge in edges {
-            add_edge_to_caves(&mut caves, edge);
-        }
+    fn pt1(caves: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(caves)
+    }
 
-        let expected = 107395;
-        assert_eq!(expected, part_2_solution(caves))
+    fn pt2(caves: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(caves)
     }
 }
+
+crate::examples!(Day12, pt1: [_1, _2, _3], pt2: [_1, _2, _3]);
@@ -1,40 +1,41 @@
 /// problem: https://adventofcode.com/2021/day/13
 /// input: "https://adventofcode.com/2021/day/13/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    grid::Grid,
+    ocr, Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 13,
     name: "Transparent Origami",
 };
-use anyhow::Result;
 use std::{collections::HashSet, fmt::Display, str::FromStr};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Axis {
     X,
     Y,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Move {
     pub axis: Axis,
     pub pivot: usize,
 }
 
 impl FromStr for Move {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (ax, co) = s
-            .split(" ")
-            .last()
-            .expect("unable to split at ' '")
-            .split_once('=')
-            .expect("unable to split at '='");
+            .strip_prefix("fold along ")
+            .and_then(|fold| fold.split_once('='))
+            .ok_or_else(|| ParseError::UnexpectedToken(s.to_owned()))?;
         let axis = match ax {
             "x" => Axis::X,
             "y" => Axis::Y,
-            _ => panic!("incorrect axis observed"),
+            _ => return Err(ParseError::UnexpectedToken(ax.to_owned())),
         };
         let coordinate: usize = co.parse()?;
         Ok(Self {
@@ -44,6 +45,7 @@ impl FromStr for Move {
     }
 }
 
+#[derive(Clone)]
 pub struct Board {
     pieces: HashSet<(usize, usize)>,
 }
@@ -80,6 +82,23 @@ impl Display for Board {
 }
 
 impl Board {
+    /// lit where there's a dot, from the origin to the furthest dot
+    fn grid(&self) -> Grid<bool> {
+        let width = self
+            .pieces
+            .iter()
+            .map(|p| p.0 + 1)
+            .max()
+            .unwrap_or_default();
+        let height = self
+            .pieces
+            .iter()
+            .map(|p| p.1 + 1)
+            .max()
+            .unwrap_or_default();
+        Grid::from_fn(width, height, |pos| self.pieces.contains(&pos))
+    }
+
     fn fold_up(&mut self, m: Move) {
         let pivot = m.pivot;
         match m.axis {
@@ -127,67 +146,92 @@ pub fn part_1_solution(board: Board, moves: Vec<Move>) -> usize {
     board.pieces.len()
 }
 
-pub fn part_2_solution(board: Board, moves: Vec<Move>) -> Board {
+/// the letters the dots spell once folded, or the dots themselves if they can't be read
+pub fn part_2_solution(board: Board, moves: Vec<Move>) -> String {
     let mut board = board;
     for m in moves {
         board.fold_up(m);
     }
-    board
+    let grid = board.grid();
+    ocr::read(&grid).unwrap_or_else(|_| ocr::draw(&grid))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use anyhow::Result;
-    use std::fs::read_to_string;
-
-    fn read_day13(path: impl AsRef<std::path::Path>) -> Result<(Board, Vec<Move>)> {
-        let file = read_to_string(path)?; // need to create this binding as lines takes a reference to a string
-        let mut lines = file.lines();
+pub fn parse(input: &str) -> Result<(Board, Vec<Move>), ParseError> {
+    let mut lines = input.lines();
 
-        let mut board = Board {
-            pieces: HashSet::new(),
-        };
-        while let Some(next_line) = lines.next() {
-            if next_line.is_empty() {
-                break;
-            }
-            let (row, col) = next_line.split_once(",").expect("unable to split at , ");
-            board.pieces.insert((row.parse()?, col.parse()?));
-        }
-
-        let mut moves: Vec<Move> = Vec::new();
-        while let Some(m) = lines.next() {
-            moves.push(m.parse()?);
+    let mut board = Board {
+        pieces: HashSet::new(),
+    };
+    for next_line in lines.by_ref() {
+        if next_line.is_empty() {
+            break;
         }
+        let (row, col) = next_line
+            .split_once(',')
+            .ok_or_else(|| ParseError::UnexpectedToken(next_line.to_owned()))?;
+        board.pieces.insert((row.parse()?, col.parse()?));
+    }
 
-        Ok((board, moves))
+    let mut moves: Vec<Move> = Vec::new();
+    for m in lines {
+        moves.push(m.parse()?);
     }
-    #[test]
-    fn example_part_1() {
-        let (board, moves) = read_day13(P.example_path("_1")).unwrap();
-        let expected = 17;
-        assert_eq!(expected, part_1_solution(board, moves))
+
+    Ok((board, moves))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const PROBLEM: Problem = P;
+    type Parsed = (Board, Vec<Move>);
+    type OutputPt1 = usize;
+    type OutputPt2 = String;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        Ok(parse(data)?)
     }
 
-    #[test]
-    fn input_part_1() {
-        let (board, moves) = read_day13(P.input_path()).unwrap();
-        let expected = 770;
-        assert_eq!(expected, part_1_solution(board, moves))
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        let (board, moves) = parsed;
+        part_1_solution(board.clone(), moves.clone())
     }
 
-    #[test]
-    fn example_part_2() {
-        let (board, moves) = read_day13(P.example_path("_1")).unwrap();
-        let board = part_2_solution(board, moves);
-        println!("{}", board);
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        let (board, moves) = parsed;
+        part_2_solution(board.clone(), moves.clone())
     }
+}
+
+crate::examples!(Day13, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
     #[test]
-    fn input_part_2() {
-        let (board, moves) = read_day13(P.input_path()).unwrap();
-        let board = part_2_solution(board, moves);
-        println!("{}", board);
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(matches!(
+            parse(&input),
+            Err(ParseError::UnexpectedToken(dot)) if dot == "9"
+        ));
+        let mut moves = input.lines().skip(3).map(Move::from_str);
+        assert!(matches!(
+            moves.next(),
+            Some(Err(ParseError::UnexpectedToken(axis))) if axis == "z"
+        ));
+        assert!(matches!(
+            moves.next(),
+            Some(Err(ParseError::UnexpectedToken(fold))) if fold == "fold along y"
+        ));
+        assert!(matches!(
+            moves.next(),
+            Some(Err(ParseError::ParseIntError(_)))
+        ));
+        assert!(matches!(
+            moves.next(),
+            Some(Err(ParseError::UnexpectedToken(fold))) if fold == "flip along y=7"
+        ));
     }
 }
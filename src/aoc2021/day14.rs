@@ -1,13 +1,19 @@
 /// problem: https://adventofcode.com/2021/day/14
 /// input: "https://adventofcode.com/2021/day/14/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 14,
     name: "Extended Polymerization",
 };
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// each pair of elements, to the two pairs it becomes once its element is inserted
+type PairMapping = HashMap<(char, char), ((char, char), (char, char))>;
 
 pub fn expand_polymer(polymer: String, polymer_map: &HashMap<String, char>) -> String {
     let chars: Vec<char> = polymer.chars().collect();
@@ -26,7 +32,7 @@ pub fn expand_polymer(polymer: String, polymer_map: &HashMap<String, char>) -> S
 
 pub fn expand_polymer_counts(
     polymer_pair_counts: HashMap<(char, char), usize>,
-    polymer_output_mapping: &HashMap<(char, char), ((char, char), (char, char))>,
+    polymer_output_mapping: &PairMapping,
 ) -> HashMap<(char, char), usize> {
     let mut expanded_pair_counts = HashMap::new();
     for (pair, count) in polymer_pair_counts {
@@ -37,10 +43,9 @@ pub fn expand_polymer_counts(
     expanded_pair_counts
 }
 
-pub fn part_1_solution(polymer: String, polymer_map: HashMap<String, char>) -> usize {
-    let mut polymer_output_mapping: HashMap<(char, char), ((char, char), (char, char))> =
-        HashMap::new();
-    for (pair, middle) in polymer_map {
+pub fn part_1_solution(polymer: &str, polymer_map: &HashMap<String, char>) -> usize {
+    let mut polymer_output_mapping: PairMapping = HashMap::new();
+    for (pair, &middle) in polymer_map {
         let mut pair = pair.chars();
         let left = pair.next().unwrap();
         let right = pair.next().unwrap();
@@ -95,10 +100,9 @@ pub fn part_1_solution_naive(polymer: String, polymer_map: HashMap<String, char>
     max_score - min_score
 }
 
-pub fn part_2_solution(polymer: String, polymer_map: HashMap<String, char>) -> usize {
-    let mut polymer_output_mapping: HashMap<(char, char), ((char, char), (char, char))> =
-        HashMap::new();
-    for (pair, middle) in polymer_map {
+pub fn part_2_solution(polymer: &str, polymer_map: &HashMap<String, char>) -> usize {
+    let mut polymer_output_mapping: PairMapping = HashMap::new();
+    for (pair, &middle) in polymer_map {
         let mut pair = pair.chars();
         let left = pair.next().unwrap();
         let right = pair.next().unwrap();
@@ -136,72 +140,101 @@ pub fn part_2_solution(polymer: String, polymer_map: HashMap<String, char>) -> u
     max_score / 2 - min_score / 2
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use anyhow::Result;
-    use std::fs::read_to_string;
-    fn read_day14(path: impl AsRef<std::path::Path>) -> Result<(String, HashMap<String, char>)> {
-        let file = read_to_string(path)?; // need to create this binding as lines takes a reference to a string
-        let mut lines = file.lines();
-
-        let polymer = lines.next().expect("chars").to_owned();
-        lines.next(); // skip empty line
-
-        let mut polymer_map = HashMap::new();
-        while let Some(next_line) = lines.next() {
-            if next_line.is_empty() {
-                break;
-            }
-            let (key, val) = next_line
-                .split_once(" -> ")
-                .expect("unable to split at -> ");
-            polymer_map.insert(key.to_owned(), val.chars().next().unwrap());
-        }
+/// the template and the pair insertion rules. every pair the template can grow
+/// needs a rule
+pub fn parse(input: &str) -> Result<(String, HashMap<String, char>), ParseError> {
+    let mut lines = input.lines();
 
-        Ok((polymer, polymer_map))
+    let polymer = match lines.next() {
+        Some(polymer) if !polymer.is_empty() => polymer.to_owned(),
+        _ => return Err(ParseError::UnexpectedEnd),
+    };
+    match lines.next() {
+        Some("") | None => {} // skip empty line
+        Some(line) => return Err(ParseError::UnexpectedToken(line.to_owned())),
     }
 
-    #[test]
-    fn example_part_1() {
-        let (polymer, polymer_map) = read_day14(P.example_path("_1")).unwrap();
-        let expected = 1588;
-        assert_eq!(expected, part_1_solution(polymer, polymer_map))
+    let mut polymer_map = HashMap::new();
+    for next_line in lines {
+        if next_line.is_empty() {
+            break;
+        }
+        let (key, val) = next_line
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::UnexpectedToken(next_line.to_owned()))?;
+        let mut middle = val.chars();
+        let (Some(middle), None) = (middle.next(), middle.next()) else {
+            return Err(ParseError::UnexpectedToken(val.to_owned()));
+        };
+        if key.chars().count() != 2 {
+            return Err(ParseError::UnexpectedToken(key.to_owned()));
+        }
+        polymer_map.insert(key.to_owned(), middle);
     }
 
-    #[test]
-    fn example_small_part_1() {
-        let (polymer, polymer_map) = read_day14(P.example_path("_small")).unwrap();
-        let expected = 1;
-        assert_eq!(expected, part_1_solution(polymer, polymer_map))
+    let mut seen = HashSet::new();
+    let mut pairs: Vec<(char, char)> = polymer.chars().tuple_windows().collect();
+    while let Some((left, right)) = pairs.pop() {
+        if seen.insert((left, right)) {
+            let &middle = polymer_map.get(&String::from_iter([left, right])).ok_or(
+                ParseError::InvalidSequence("a pair without an insertion rule"),
+            )?;
+            pairs.extend([(left, middle), (middle, right)]);
+        }
     }
 
-    #[test]
-    fn input_part_1() {
-        let (polymer, polymer_map) = read_day14(P.input_path()).unwrap();
-        let expected = 2509;
-        assert_eq!(expected, part_1_solution(polymer, polymer_map))
-    }
+    Ok((polymer, polymer_map))
+}
 
-    #[test]
-    fn example_part_2() {
-        let (polymer, polymer_map) = read_day14(P.example_path("_1")).unwrap();
+pub struct Day14;
+
+impl Solution for Day14 {
+    const PROBLEM: Problem = P;
+    type Parsed = (String, HashMap<String, char>);
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-        let expected = 2188189693529;
-        assert_eq!(expected, part_2_solution(polymer, polymer_map))
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        Ok(parse(data)?)
     }
 
-    #[test]
-    fn example_small_part_2() {
-        let (polymer, polymer_map) = read_day14(P.example_path("_small")).unwrap();
-        let expected = 1;
-        assert_eq!(expected, part_2_solution(polymer, polymer_map))
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        let (polymer, polymer_map) = parsed;
+        part_1_solution(polymer, polymer_map)
+    }
+
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        let (polymer, polymer_map) = parsed;
+        part_2_solution(polymer, polymer_map)
     }
+}
+
+crate::examples!(Day14, pt1: [_1, _small], pt2: [_1, _small]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
     #[test]
-    fn input_part_2() {
-        let (polymer, polymer_map) = read_day14(P.input_path()).unwrap();
-        let expected = 2827627697643;
-        assert_eq!(expected, part_2_solution(polymer, polymer_map))
+    fn malformed() {
+        let example = crate::read_input(P.example_path("_1")).unwrap();
+        assert!(parse(&example).is_ok());
+        assert!(matches!(
+            parse(&example.replace("CH -> B\n", "")),
+            Err(ParseError::InvalidSequence(_))
+        ));
+        assert!(matches!(
+            parse("NNCB\n\nNN => C"),
+            Err(ParseError::UnexpectedToken(rule)) if rule == "NN => C"
+        ));
+        assert!(matches!(
+            parse("NNCB\n\nNNN -> C"),
+            Err(ParseError::UnexpectedToken(pair)) if pair == "NNN"
+        ));
+        assert!(matches!(
+            parse("NNCB\n\nNN -> "),
+            Err(ParseError::UnexpectedToken(middle)) if middle.is_empty()
+        ));
+        assert!(matches!(parse(""), Err(ParseError::UnexpectedEnd)));
     }
 }
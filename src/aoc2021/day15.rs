@@ -1,153 +1,102 @@
 /// problem: https://adventofcode.com/2021/day/15
 /// input: "https://adventofcode.com/2021/day/15/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 15,
     name: "Chiton",
 };
 
-use itertools::Itertools;
-
-fn expand_graph_5_times(graph: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
-    let size = graph.len();
-    assert_eq!(size, graph[0].len());
+use crate::{
+    grid::{Grid, Pos},
+    image::{gradient, Export, Rgb, RED},
+    search::astar,
+};
 
-    let mut expanded_graph: Vec<Vec<usize>> = vec![vec![0; size * 5]; size * 5];
+fn expand_graph_5_times(graph: &Grid<usize>) -> Grid<usize> {
+    let (width, height) = (graph.width(), graph.height());
+    Grid::from_fn(width * 5, height * 5, |(x, y)| {
+        let tile = x / width + y / height;
+        ((graph[(x % width, y % height)] + tile - 1) % 9) + 1
+    })
+}
 
-    for (row, col) in (0..5).cartesian_product(0..5) {
-        for (x, y) in (0..size).cartesian_product(0..size) {
-            let new_x = row * size + x;
-            let new_y = col * size + y;
-            let val = ((graph[x][y] + row + col - 1) % 9) + 1;
-            expanded_graph[new_x][new_y] = val;
-        }
-    }
-    expanded_graph
+/// the lowest total risk from the top left to the bottom right, and the way there.
+/// the start doesn't count
+fn safest(chiton_heights: &Grid<usize>) -> (usize, Vec<Pos>) {
+    let end = (chiton_heights.width() - 1, chiton_heights.height() - 1);
+    let neighbours = |&node: &Pos| {
+        chiton_heights
+            .neighbours4(node)
+            .map(|next| (next, chiton_heights[next]))
+    };
+    // every step costs at least 1 so the manhattan distance never overestimates
+    let heuristic = |&(x, y): &Pos| (end.0 - x) + (end.1 - y);
+    astar([(0, 0)], neighbours, heuristic, |&node| node == end)
+        .expect("parse never returns an empty grid, and every cell of a grid is reachable")
 }
 
-fn get_neighbours(coordinate: (usize, usize), xmax: usize, ymax: usize) -> Vec<(usize, usize)> {
-    let (x, y) = coordinate;
-    let mut neighbours = Vec::new();
-    if x > 0 {
-        neighbours.push((x - 1, y));
-    }
-    if x < xmax {
-        neighbours.push((x + 1, y));
-    }
-    if y > 0 {
-        neighbours.push((x, y - 1));
-    }
-    if y < ymax {
-        neighbours.push((x, y + 1));
-    }
-    neighbours
+/// the lowest total risk from the top left to the bottom right. the start doesn't count
+pub fn part_1_solution(chiton_heights: &Grid<usize>) -> usize {
+    safest(chiton_heights).0
 }
 
-/// return the shortest path as per djikstra
-fn djikstra(
-    graph: &Vec<Vec<usize>>,
-    start: (usize, usize),
-    end: (usize, usize),
-) -> Vec<Vec<usize>> {
-    // start is usually 0,0
+pub fn part_2_solution(chiton_heights: &Grid<usize>) -> usize {
+    part_1_solution(&expand_graph_5_times(chiton_heights))
+}
 
-    let xmax = graph.len() - 1;
-    let ymax = graph[0].len() - 1;
+/// risks are 1 to 9. a 0 would make a free step, which the A* heuristic assumes can't happen
+pub fn parse(input: &str) -> Result<Grid<usize>, Error> {
+    Grid::parse_with(input, |c| {
+        c.to_digit(10).filter(|&d| d > 0).map(|d| d as usize)
+    })
+}
 
-    // initialize the distances
-    let mut distances = vec![vec![usize::MAX; ymax + 1]; xmax + 1];
-    distances[start.0][start.1] = graph[start.0][start.1];
+pub struct Day15;
 
-    // initialize the visited
-    let mut visited = vec![vec![false; ymax + 1]; xmax + 1];
-    visited[start.0][start.1] = true;
+impl Solution for Day15 {
+    const PROBLEM: Problem = P;
+    type Parsed = Grid<usize>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    let mut frontier = get_neighbours(start, xmax, ymax); // TODO: use a queue/heap
-    for node in frontier.iter() {
-        distances[node.0][node.1] = distances[start.0][start.1] + graph[node.0][node.1];
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
-    let mut next_nearest_node = start;
-    while next_nearest_node != end {
-        // get next best candidate
-        let mut min_dist = usize::MAX;
-        next_nearest_node = start;
-        for node in frontier.iter() {
-            if distances[node.0][node.1] < min_dist {
-                min_dist = distances[node.0][node.1];
-                next_nearest_node = *node;
-            }
-        }
-        if next_nearest_node == start {
-            panic!("nearest node not found");
-        }
 
-        // visit node
-        visited[next_nearest_node.0][next_nearest_node.1] = true;
-        // add to frontier and update distances
-        for node in get_neighbours(next_nearest_node, xmax, ymax) {
-            if distances[node.0][node.1]
-                > distances[next_nearest_node.0][next_nearest_node.1] + graph[node.0][node.1]
-            {
-                distances[node.0][node.1] =
-                    distances[next_nearest_node.0][next_nearest_node.1] + graph[node.0][node.1];
-                if distances[node.0][node.1] < graph[node.0][node.1] {
-                    println!("shouldn't be able to get here");
-                }
-            }
-            if !visited[node.0][node.1] {
-                frontier.push(node);
-            }
-        }
-        frontier.retain(|node| *node != next_nearest_node);
+    fn pt1(chiton_heights: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(chiton_heights)
     }
 
-    distances
+    fn pt2(chiton_heights: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(chiton_heights)
+    }
 }
 
-pub fn part_1_solution(chiton_heights: Vec<Vec<usize>>) -> usize {
-    let start = (0, 0);
-    let end = (chiton_heights.len() - 1, chiton_heights[0].len() - 1);
-
-    let distances = djikstra(&chiton_heights, start, end);
-    distances[end.0][end.1] - distances[start.0][start.1] // apparently the start doesn't count
+impl Export for Day15 {
+    /// the risk of the full cave of part 2, dark for low and light for high,
+    /// with the safest path across it
+    fn export(chiton_heights: &Self::Parsed, emit: &mut dyn FnMut(Grid<Rgb>)) {
+        let cave = expand_graph_5_times(chiton_heights);
+        let mut image =
+            cave.map(|&risk| gradient([15, 30, 60], [120, 200, 230], (risk - 1) as f64 / 8.0));
+        let (_, path) = safest(&cave);
+        for pos in path {
+            image[pos] = RED;
+        }
+        emit(image);
+    }
 }
 
-pub fn part_2_solution(chiton_heights: Vec<Vec<usize>>) -> usize {
-    let expanded_chiton_heights = expand_graph_5_times(chiton_heights);
-    let start = (0, 0);
-    let end = (
-        expanded_chiton_heights.len() - 1,
-        expanded_chiton_heights[0].len() - 1,
-    );
-
-    let distances = djikstra(&expanded_chiton_heights, start, end);
-    distances[end.0][end.1] - distances[start.0][start.1] // apparently the start doesn't count
-}
+crate::examples!(Day15, pt1: [_1, _2], pt2: [_1]);
 
 #[cfg(test)]
 mod test {
-    use crate::read_to_vec_per_line;
-
     use super::*;
 
-    #[test]
-    fn test_neighbours() {
-        let xmax = 12;
-        let ymax = 10;
-        let node = (0, 0);
-        let expected = vec![(1, 0), (0, 1)];
-        assert_eq!(expected, get_neighbours(node, xmax, ymax));
-
-        let node = (1, 1);
-        let expected = vec![(0, 1), (2, 1), (1, 0), (1, 2)];
-        assert_eq!(expected, get_neighbours(node, xmax, ymax));
-
-        let node = (12, 10);
-        let expected = vec![(11, 10), (12, 9)];
-        assert_eq!(expected, get_neighbours(node, xmax, ymax));
-    }
-
     #[test]
     fn test_expand_graph_5_times() {
         let input = vec![vec![1, 1], vec![1, 1]];
@@ -163,7 +112,10 @@ mod test {
             vec![5, 5, 6, 6, 7, 7, 8, 8, 9, 9],
             vec![5, 5, 6, 6, 7, 7, 8, 8, 9, 9],
         ];
-        assert_eq!(expected, expand_graph_5_times(input));
+        assert_eq!(
+            Grid::from(expected),
+            expand_graph_5_times(&Grid::from(input))
+        );
 
         let input = vec![vec![9, 1], vec![1, 1]];
         let expected = vec![
@@ -178,17 +130,10 @@ mod test {
             vec![4, 5, 5, 6, 6, 7, 7, 8, 8, 9],
             vec![5, 5, 6, 6, 7, 7, 8, 8, 9, 9],
         ];
-        assert_eq!(expected, expand_graph_5_times(input));
-    }
-
-    #[test]
-    fn example_part_1() {
-        let input = read_to_vec_per_line(P.example_path("_1"), |c| {
-            Some(c.to_digit(10).expect("should be usize") as usize)
-        })
-        .expect("unable to open file");
-        let expected = 40;
-        assert_eq!(expected, part_1_solution(input));
+        assert_eq!(
+            Grid::from(expected),
+            expand_graph_5_times(&Grid::from(input))
+        );
     }
 
     #[test]
@@ -201,37 +146,35 @@ mod test {
             vec![10, 10, 10, 10, 1],
         ];
         let expected = 10;
-        assert_eq!(expected, part_1_solution(input));
+        assert_eq!(expected, part_1_solution(&Grid::from(input)));
     }
 
     #[test]
-    fn input_part_1() {
-        let input = read_to_vec_per_line(P.input_path(), |c| {
-            Some(c.to_digit(10).expect("should be usize") as usize)
-        })
-        .expect("unable to open file");
-        let expected = 487;
-        assert_eq!(expected, part_1_solution(input));
+    fn empty() {
+        assert!(matches!(
+            Day15::parse(""),
+            Err(Error::ParseError(ParseError::UnexpectedEnd))
+        ));
     }
 
     #[test]
-    fn example_part_2() {
-        let input = read_to_vec_per_line(P.example_path("_1"), |c| {
-            Some(c.to_digit(10).expect("should be usize") as usize)
-        })
-        .expect("unable to open file");
-        let expected = 315;
-        assert_eq!(expected, part_2_solution(input));
+    fn zero_risk() {
+        assert!(matches!(
+            parse("19\n01"),
+            Err(Error::ParseError(ParseError::Located { location, .. }))
+                if (location.line, location.col) == (2, 1)
+        ));
     }
 
     #[test]
-    fn input_part_2() {
-        // TODO: improve performance
-        let input = read_to_vec_per_line(P.input_path(), |c| {
-            Some(c.to_digit(10).expect("should be usize") as usize)
-        })
-        .expect("unable to open file");
-        let expected = 2821;
-        assert_eq!(expected, part_2_solution(input));
+    fn export_edge_risks() {
+        let cave = parse("11\n19").unwrap();
+        let mut frames = Vec::new();
+        Day15::export(&cave, &mut |image| frames.push(image));
+        let image = &frames[0];
+        assert_eq!(image[(9, 9)], RED);
+        // a 9, and the 1 in the corner that wraps round to 9 in the last tile
+        assert_eq!(image[(1, 1)], image[(8, 8)]);
+        assert_ne!(image[(1, 1)], RED);
     }
 }
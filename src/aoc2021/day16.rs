@@ -1,292 +1,269 @@
 /// problem: https://adventofcode.com/2021/day/16
 /// input: "https://adventofcode.com/2021/day/16/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    bits::{self, BitReader, BitWriter},
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 16,
     name: "Packet Decoder",
 };
 
 use itertools::Itertools;
-use std::str::{Chars, FromStr};
+use std::str::FromStr;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TypeID {
     Literal,
     Operation(u8),
 }
 
+/// how an operator marks where its subpackets end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthType {
+    /// a 15 bit count of the bits the subpackets take up
+    Bits,
+    /// an 11 bit count of the subpackets
+    Count,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Packet {
     length: usize,
     type_id: TypeID,
     version: u8,
     value: usize,
-    subpackets: Option<Vec<Packet>>,
+    subpackets: Option<(LengthType, Vec<Packet>)>,
 }
 
 impl Packet {
     pub fn sum_versions(&self) -> usize {
-        match self.type_id {
-            TypeID::Literal => self.version as usize,
-            TypeID::Operation(_) => {
-                self.version as usize
-                    + match &self.subpackets {
-                        Some(subpackets) => {
-                            subpackets.iter().map(|p| p.sum_versions()).sum::<usize>()
-                        }
-                        None => 0,
-                    }
+        self.version as usize
+            + match &self.subpackets {
+                Some((_, subpackets)) => subpackets.iter().map(|p| p.sum_versions()).sum(),
+                None => 0,
             }
-        }
     }
 
-    pub fn from_chars(chars: &mut Chars) -> anyhow::Result<Self> {
-        // let b = &mut bin_chars; // TODO: why the fuck do i need to do this on a ref
-        let version = u8::from_str_radix(chars.take(3).collect::<String>().as_str(), 2)
-            .expect("unable to parse version from binary chars");
-        let type_id = match u8::from_str_radix(chars.take(3).collect::<String>().as_str(), 2)
-            .expect("unable to parse type_id from binary chars")
-        {
+    pub fn from_bits(bits: &mut BitReader) -> Result<Self, ParseError> {
+        let start = bits.pos();
+        let version = bits.read_bits(3)? as u8;
+        let type_id = match bits.read_bits(3)? {
             4 => TypeID::Literal,
-            v => TypeID::Operation(v),
+            v => TypeID::Operation(v as u8),
         };
 
-        let mut length = 6; // length of header
-        let subpackets = match type_id {
-            TypeID::Literal => None,
-            TypeID::Operation(_) => {
-                length += 1;
-                match chars.next().expect("unexpected end of string") {
-                    '0' => {
-                        let length_of_remaining_data =
-                            usize::from_str_radix(chars.take(15).collect::<String>().as_str(), 2)?;
-                        length += 15 + length_of_remaining_data;
-
-                        let mut idx = 0;
-                        let mut packets = Vec::new();
-                        while idx < length_of_remaining_data {
-                            let next_packet = Packet::from_chars(chars)?;
-                            idx += next_packet.length;
-                            packets.push(next_packet);
-                        }
-                        Some(packets)
+        let (value, subpackets) = match type_id {
+            TypeID::Literal => {
+                // groups of 4 bits, each led by a bit saying if another group follows
+                let mut value: usize = 0;
+                loop {
+                    let more = bits.read_bit()?;
+                    // another group would push set bits out of the top
+                    if value.leading_zeros() < 4 {
+                        return Err(ParseError::InvalidSequence("literal too large"));
                     }
-                    '1' => {
-                        let num_of_sub_packets =
-                            usize::from_str_radix(chars.take(11).collect::<String>().as_str(), 2)
-                                .unwrap();
-                        length += 11;
-                        let mut packets = Vec::with_capacity(num_of_sub_packets);
-                        for _ in 0..num_of_sub_packets {
-                            let next_packet = Packet::from_chars(chars)?;
-                            length += next_packet.length;
-                            packets.push(next_packet);
-                        }
-                        Some(packets)
+                    value = value << 4 | bits.read_bits(4)? as usize;
+                    if !more {
+                        break;
                     }
-                    c => panic!("unexpected char found in binary string: {c}"),
                 }
+                (value, None)
             }
-        };
-        let value = match type_id {
-            TypeID::Literal => {
-                let mut val = String::new();
-                while chars.next().expect("unexpected end of string") != '0' {
-                    val.extend(chars.take(4));
-                    length += 5;
-                }
-                // consume last packet
-                val.extend(chars.take(4));
-                length += 5;
-                usize::from_str_radix(val.as_str(), 2).expect("unable to parse data into digit")
+            TypeID::Operation(op) => {
+                let mut packets = Vec::new();
+                let length_type = if bits.read_bit()? {
+                    let num_of_sub_packets = bits.read_bits(11)?;
+                    for _ in 0..num_of_sub_packets {
+                        packets.push(Packet::from_bits(bits)?);
+                    }
+                    LengthType::Count
+                } else {
+                    let length_of_remaining_data = bits.read_bits(15)? as usize;
+                    let end = bits.pos() + length_of_remaining_data;
+                    while bits.pos() < end {
+                        packets.push(Packet::from_bits(bits)?);
+                    }
+                    LengthType::Bits
+                };
+                let value =
+                    evaluate(op, &packets).ok_or(ParseError::InvalidSequence("value too large"))?;
+                (value, Some((length_type, packets)))
             }
-            TypeID::Operation(op) => match op {
-                0 => subpackets.as_ref().unwrap().iter().map(|p| p.value).sum(),
-                1 => subpackets
-                    .as_ref()
-                    .unwrap()
-                    .iter()
-                    .map(|p| p.value)
-                    .product(),
-                2 => subpackets
-                    .as_ref()
-                    .unwrap()
-                    .iter()
-                    .fold(usize::MAX, |min, p| min.min(p.value)),
-                3 => subpackets
-                    .as_ref()
-                    .unwrap()
-                    .iter()
-                    .fold(0, |max, p| max.max(p.value)),
-                4 => unreachable!("Literal should have already been captured"),
-                5 => {
-                    let mut ps = subpackets.as_ref().unwrap().iter().map(|p| p.value);
-                    usize::from(ps.next() > ps.next())
-                }
-                6 => {
-                    let mut ps = subpackets.as_ref().unwrap().iter().map(|p| p.value);
-                    usize::from(ps.next() < ps.next())
-                }
-                7 => {
-                    let mut ps = subpackets.as_ref().unwrap().iter().map(|p| p.value);
-                    usize::from(ps.next() == ps.next())
-                }
-                _ => unreachable!("there shouldn't be an int heres"),
-            },
         };
 
         Ok(Self {
-            length,
+            length: bits.pos() - start,
             version,
             type_id,
             value,
             subpackets,
         })
     }
+
+    /// the packet as hex, padded with zeros to a whole number of bytes
+    pub fn to_hex(&self) -> String {
+        let mut bits = BitWriter::new();
+        self.write(&mut bits);
+        bits::encode_hex(&bits.into_bytes())
+    }
+
+    fn write(&self, bits: &mut BitWriter) {
+        bits.write_bits(self.version as u64, 3);
+        match (&self.type_id, &self.subpackets) {
+            (TypeID::Literal, _) => {
+                bits.write_bits(4, 3);
+                let width = usize::BITS - self.value.leading_zeros();
+                let groups = width.max(1).div_ceil(4);
+                for i in (0..groups).rev() {
+                    bits.write_bit(i > 0);
+                    bits.write_bits((self.value >> (4 * i)) as u64 & 0xF, 4);
+                }
+            }
+            (TypeID::Operation(op), Some((length_type, packets))) => {
+                bits.write_bits(*op as u64, 3);
+                let mut body = BitWriter::new();
+                for packet in packets {
+                    packet.write(&mut body);
+                }
+                match length_type {
+                    LengthType::Bits => {
+                        bits.write_bit(false);
+                        bits.write_bits(body.len() as u64, 15);
+                    }
+                    LengthType::Count => {
+                        bits.write_bit(true);
+                        bits.write_bits(packets.len() as u64, 11);
+                    }
+                }
+                bits.append(&body);
+            }
+            (TypeID::Operation(_), None) => unreachable!("operators always have subpackets"),
+        }
+    }
+}
+
+/// the value of an operator over its subpackets. `None` if a sum or product doesn't fit
+fn evaluate(op: u8, subpackets: &[Packet]) -> Option<usize> {
+    let mut values = subpackets.iter().map(|p| p.value);
+    match op {
+        0 => values.try_fold(0, usize::checked_add),
+        1 => values.try_fold(1, usize::checked_mul),
+        2 => Some(values.min().unwrap_or(usize::MAX)),
+        3 => Some(values.max().unwrap_or(0)),
+        5 => Some(usize::from(values.next() > values.next())),
+        6 => Some(usize::from(values.next() < values.next())),
+        7 => Some(usize::from(values.next() == values.next())),
+        _ => unreachable!("type ids are 3 bits and 4 is a literal"),
+    }
 }
 
 impl FromStr for Packet {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(hex_string: &str) -> anyhow::Result<Self> {
-        let bin_string = hex_string
-            .chars()
-            .map(|v| format!("{:04b}", v.to_digit(16).expect("char not hex char.")))
-            .join("");
-        let chars = &mut bin_string.chars();
-        Packet::from_chars(chars)
+    fn from_str(hex_string: &str) -> Result<Self, Self::Err> {
+        let bytes = bits::decode_hex(hex_string)?;
+        Packet::from_bits(&mut BitReader::new(&bytes))
     }
 }
 
-pub fn part_1_solution(hex_string: String) -> usize {
-    let encoding =
-        Packet::from_str(hex_string.as_str()).expect("unable to build encoding from hex_string");
-    encoding.sum_versions()
-}
-pub fn part_2_solution(hex_string: String) -> usize {
-    let encoding =
-        Packet::from_str(hex_string.as_str()).expect("unable to build encoding from hex_string");
-    encoding.value
+pub struct Day16;
+
+impl Solution for Day16 {
+    const PROBLEM: Problem = P;
+    type Parsed = Packet;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        Ok(data.trim().parse()?)
+    }
+
+    fn pt1(packet: &Self::Parsed) -> Self::OutputPt1 {
+        packet.sum_versions()
+    }
+
+    fn pt2(packet: &Self::Parsed) -> Self::OutputPt2 {
+        packet.value
+    }
 }
 
+crate::examples!(Day16, pt1: [_1, _2, _3], pt2: []);
+
 #[cfg(test)]
 mod test {
     use std::fs::read_to_string;
 
     use super::*;
 
-    #[test]
-    fn example1_part_1() {
-        let hex_string = read_to_string(P.example_path("_1"))
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .to_owned();
-
-        let expected = 6;
-        assert_eq!(expected, part_1_solution(hex_string))
+    fn part_1_solution(hex_string: &str) -> usize {
+        Day16::pt1(&Day16::parse(hex_string).unwrap())
     }
 
-    #[test]
-    fn example2_part_1() {
-        let hex_string = read_to_string(P.example_path("_2"))
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .to_owned();
-
-        let expected = 9;
-        assert_eq!(expected, part_1_solution(hex_string))
+    fn part_2_solution(hex_string: &str) -> usize {
+        Day16::pt2(&Day16::parse(hex_string).unwrap())
     }
 
-    #[test]
-    fn example3_part_1() {
-        let hex_string = read_to_string(P.example_path("_3"))
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .to_owned();
-
-        let expected = 14;
-        assert_eq!(expected, part_1_solution(hex_string))
-    }
     #[test]
     fn example_others_part_1() {
-        let hex_string = "8A004A801A8002F478".to_owned();
+        let hex_string = "8A004A801A8002F478";
         let expected = 16;
         assert_eq!(expected, part_1_solution(hex_string));
 
-        let hex_string = "620080001611562C8802118E34".to_owned();
+        let hex_string = "620080001611562C8802118E34";
         let expected = 12;
         assert_eq!(expected, part_1_solution(hex_string));
 
-        let hex_string = "C0015000016115A2E0802F182340".to_owned();
+        let hex_string = "C0015000016115A2E0802F182340";
         let expected = 23;
         assert_eq!(expected, part_1_solution(hex_string));
 
-        let hex_string = "A0016C880162017C3686B18A3D4780".to_owned();
+        let hex_string = "A0016C880162017C3686B18A3D4780";
         let expected = 31;
         assert_eq!(expected, part_1_solution(hex_string));
 
-        let hex_string = "9C005AC2F8F0".to_owned();
+        let hex_string = "9C005AC2F8F0";
         let expected = 16;
         assert_eq!(expected, part_1_solution(hex_string));
     }
 
-    #[test]
-    fn input_part_1() {
-        let hex_string = read_to_string(P.input_path())
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .to_owned();
-
-        let expected = 953;
-        assert_eq!(expected, part_1_solution(hex_string))
-    }
-
     #[test]
     fn example_part_2() {
-        let hex_string = "C200B40A82".to_owned();
+        let hex_string = "C200B40A82";
         let expected = 3;
         assert_eq!(expected, part_2_solution(hex_string));
 
-        let hex_string = "04005AC33890".to_owned();
+        let hex_string = "04005AC33890";
         let expected = 54;
         assert_eq!(expected, part_2_solution(hex_string));
 
-        let hex_string = "880086C3E88112".to_owned();
+        let hex_string = "880086C3E88112";
         let expected = 7;
         assert_eq!(expected, part_2_solution(hex_string));
 
-        let hex_string = "CE00C43D881120".to_owned();
+        let hex_string = "CE00C43D881120";
         let expected = 9;
         assert_eq!(expected, part_2_solution(hex_string));
 
-        let hex_string = "D8005AC2A8F0".to_owned();
+        let hex_string = "D8005AC2A8F0";
         let expected = 1;
         assert_eq!(expected, part_2_solution(hex_string));
 
-        let hex_string = "F600BC2D8F".to_owned();
+        let hex_string = "F600BC2D8F";
         let expected = 0;
         assert_eq!(expected, part_2_solution(hex_string));
 
-        let hex_string = "9C005AC2F8F0".to_owned();
+        let hex_string = "9C005AC2F8F0";
         let expected = 0;
         assert_eq!(expected, part_2_solution(hex_string));
 
-        let hex_string = "9C0141080250320F1802104A08".to_owned();
+        let hex_string = "9C0141080250320F1802104A08";
         let expected = 1;
         assert_eq!(expected, part_2_solution(hex_string));
     }
 
-    #[test]
-    fn input_part_2() {
-        let hex_string = read_to_string(P.input_path())
-            .unwrap()
-            .trim() // get rid of trailing blank line
-            .to_owned();
-
-        let expected = 246225449979;
-        assert_eq!(expected, part_2_solution(hex_string))
-    }
-
     #[test]
     fn hex_bin() {
         let hex_string = "2";
@@ -298,4 +275,77 @@ mod test {
         println!("hex {}", hex_string);
         println!("bin {}", bin_string);
     }
+
+    #[test]
+    fn round_trip() {
+        let examples = ["_1", "_2", "_3"].map(|id| {
+            read_to_string(P.example_path(id))
+                .unwrap()
+                .trim()
+                .to_owned()
+        });
+        let others = [
+            "D2FE28",
+            "38006F45291200",
+            "EE00D40C823060",
+            "8A004A801A8002F478",
+            "620080001611562C8802118E34",
+            "C0015000016115A2E0802F182340",
+            "A0016C880162017C3686B18A3D4780",
+            "9C0141080250320F1802104A08",
+        ];
+        for hex_string in examples.iter().map(String::as_str).chain(others) {
+            let packet = Packet::from_str(hex_string).unwrap();
+            let encoded = packet.to_hex();
+            assert_eq!(encoded, hex_string);
+            assert_eq!(Packet::from_str(&encoded).unwrap(), packet);
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        let input = read_to_string(P.fixture_path("_malformed")).unwrap();
+        let mut lines = input.lines().map(Packet::from_str);
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedChar('G')))
+        ));
+        assert!(matches!(lines.next(), Some(Err(ParseError::UnexpectedEnd))));
+    }
+
+    #[test]
+    fn long_literal() {
+        // sixteen groups of 1111, the most that fits
+        let packet = Packet::from_str("13FFFFFFFFFFFFFFFFFFBC").unwrap();
+        assert_eq!(packet.value, u64::MAX as usize);
+        // seventeen groups, 0001 then sixteen 0000s
+        assert!(matches!(
+            Packet::from_str("123084210842108421084000"),
+            Err(ParseError::InvalidSequence(_))
+        ));
+    }
+
+    #[test]
+    fn overflowing_operators() {
+        let max = Packet {
+            length: 0,
+            type_id: TypeID::Literal,
+            version: 0,
+            value: usize::MAX,
+            subpackets: None,
+        };
+        for op in [0, 1] {
+            let packet = Packet {
+                length: 0,
+                type_id: TypeID::Operation(op),
+                version: 0,
+                value: 0,
+                subpackets: Some((LengthType::Count, vec![max.clone(), max.clone()])),
+            };
+            assert!(matches!(
+                Packet::from_str(&packet.to_hex()),
+                Err(ParseError::InvalidSequence(_))
+            ));
+        }
+    }
 }
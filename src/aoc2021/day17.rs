@@ -1,51 +1,39 @@
 /// problem: https://adventofcode.com/2021/day/17
 /// input: "https://adventofcode.com/2021/day/17/input"
 use itertools::Itertools;
+use std::collections::HashMap;
 
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::Error,
+    geom::Point2,
+    visualize::{Cell, Color, Frame, Visualize},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 17,
     name: "Trick Shot",
 };
 
-#[derive(Debug, Clone, Copy)]
-pub struct Coord(isize, isize);
-
-impl std::ops::Add for Coord {
-    type Output = Coord;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Coord(self.0 + rhs.0, self.1 + rhs.1)
-    }
-}
-
-impl std::ops::AddAssign for Coord {
-    fn add_assign(&mut self, rhs: Self) {
-        self.0 += rhs.0;
-        self.1 += rhs.1;
-    }
-}
-
 pub struct Projectile {
-    position: Coord,
-    velocity: Coord,
-    acceleration: Coord,
+    position: Point2<isize>,
+    velocity: Point2<isize>,
+    acceleration: Point2<isize>,
 }
 
 impl Projectile {
-    pub fn new(initial_velocity: Coord) -> Self {
+    pub fn new(initial_velocity: Point2<isize>) -> Self {
         Self {
-            position: Coord(0, 0), // initial position is always 0
+            position: Point2::ORIGIN, // initial position is always 0
             velocity: initial_velocity,
-            acceleration: Coord(-initial_velocity.0.signum(), -1),
+            acceleration: Point2::new(-initial_velocity.x.signum(), -1),
         }
     }
 
     pub fn step(&mut self) {
         self.position += self.velocity;
         self.velocity += self.acceleration;
-        self.acceleration.0 = -self.velocity.0.signum()
+        self.acceleration.x = -self.velocity.x.signum()
     }
 }
 
@@ -78,20 +66,38 @@ pub struct TargetArea {
 }
 
 impl TargetArea {
-    pub fn is_in_area(&self, position: Coord) -> bool {
-        let (x, y) = (position.0, position.1);
+    pub fn is_in_area(&self, position: Point2<isize>) -> bool {
+        let Point2 { x, y } = position;
         x >= self.xmin && x <= self.xmax && y >= self.ymin && y <= self.ymax
     }
     pub fn is_impossible(&self, p: &Projectile) -> bool {
         // x position out of range
-        p.position.0.abs() > self.xmax.abs().max(self.xmin.abs()) ||
+        p.position.x.abs() > self.xmax.abs().max(self.xmin.abs()) ||
         // yvelocity is down and we're below the range
-        (p.velocity.1 < 0 && p.position.1 < self.ymin) ||
+        (p.velocity.y < 0 && p.position.y < self.ymin) ||
         // xvel is 0 and not in range 
-        (p.velocity.0 == 0 && (p.position.0 > self.xmax || p.position.0 < self.xmin))
+        (p.velocity.x == 0 && (p.position.x > self.xmax || p.position.x < self.xmin))
+    }
+
+    /// each position of a probe launched at `velocity`, up to the first in the area.
+    /// `None` if it misses
+    pub fn trajectory(&self, velocity: Point2<isize>) -> Option<Vec<Point2<isize>>> {
+        let mut p = Projectile::new(velocity);
+        let mut path = Vec::new();
+        for _ in 0..1000 {
+            p.step();
+            path.push(p.position);
+            if self.is_in_area(p.position) {
+                return Some(path);
+            }
+            if self.is_impossible(&p) {
+                return None;
+            }
+        }
+        None
     }
 
-    pub fn candidate_velocities(&self) -> impl Iterator<Item = Coord> {
+    pub fn candidate_velocities(&self) -> impl Iterator<Item = Point2<isize>> {
         let max_val = 500;
         let xmin = if self.xmax > 0 && self.xmin > 0 {
             0
@@ -104,122 +110,130 @@ impl TargetArea {
             max_val
         };
         // TODO: sort appropriately e.g. start at 0 for x and y and move out
-        (xmin..xmax)
-            .sorted()
-            .flat_map(move |x| (1..max_val).chain(-max_val..1).map(move |y| Coord(x, y)))
+        (xmin..xmax).sorted().flat_map(move |x| {
+            (1..max_val)
+                .chain(-max_val..1)
+                .map(move |y| Point2::new(x, y))
+        })
     }
 }
 
-pub fn part_1_solution(target_area: TargetArea) -> isize {
-    let mut global_best_height = 0;
-    for vel in target_area.candidate_velocities() {
-        let mut p = Projectile::new(vel);
-        let mut best_height = 0;
-        for _ in 0..1000 {
-            p.step();
-            if p.position.1 > best_height {
-                best_height = p.position.1;
-            }
-            if target_area.is_in_area(p.position) {
-                if best_height > global_best_height {
-                    global_best_height = best_height;
-                }
-                break;
-            }
-            if target_area.is_impossible(&p) {
-                break;
-            }
-        }
-    }
-    global_best_height
+/// the highest point of a path, counting the launch at 0
+fn height(path: &[Point2<isize>]) -> isize {
+    path.iter().map(|p| p.y).max().unwrap_or_default().max(0)
 }
 
-pub fn part_2_solution(target_area: TargetArea) -> isize {
-    let mut res = 0;
-    for vel in target_area.candidate_velocities() {
-        let mut p = Projectile::new(vel);
-        for _ in 0..1000 {
-            p.step();
-            if target_area.is_in_area(p.position) {
-                res += 1;
-                break;
-            }
-            if target_area.is_impossible(&p) {
-                break;
-            }
-        }
-    }
-    res
+pub fn part_1_solution(target_area: &TargetArea) -> isize {
+    target_area
+        .candidate_velocities()
+        .filter_map(|vel| target_area.trajectory(vel))
+        .map(|path| height(&path))
+        .max()
+        .unwrap_or_default()
 }
 
-#[cfg(test)]
-mod test {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    fn read_day_17(path: impl AsRef<std::path::Path>) -> anyhow::Result<TargetArea> {
-        let file = read_to_string(path)?;
-        let mut splits = file
-            .trim() // get rid of trailing blank line
-            .split(" ");
-
-        // target area: x=20..30, y=-10..-5
-        // skip 2
-        splits.next();
-        splits.next();
-
-        let mut temp_xs = splits.next().unwrap().split("=");
-        temp_xs.next();
-        temp_xs = temp_xs.next().unwrap().split(",");
-        let mut xs = temp_xs.next().unwrap().split("..");
-
-        let mut temp_ys = splits.next().unwrap().split("=");
-        temp_ys.next();
-        let mut ys = temp_ys.next().unwrap().split("..");
-
-        Ok(TargetArea {
-            xmin: xs.next().unwrap().parse()?,
-            xmax: xs.next().unwrap().parse()?,
-            ymin: ys.next().unwrap().parse()?,
-            ymax: ys.next().unwrap().parse()?,
-        })
-    }
+pub fn part_2_solution(target_area: &TargetArea) -> isize {
+    target_area
+        .candidate_velocities()
+        .filter(|&vel| target_area.trajectory(vel).is_some())
+        .count() as isize
+}
 
-    #[test]
-    fn example_part_1() {
-        let target_area = read_day_17(P.example_path("_1")).unwrap();
-        let expected = 45;
+pub fn parse(input: &str) -> anyhow::Result<TargetArea> {
+    let mut splits = input
+        .trim() // get rid of trailing blank line
+        .split(" ");
+
+    // target area: x=20..30, y=-10..-5
+    // skip 2
+    splits.next();
+    splits.next();
+
+    let mut temp_xs = splits.next().unwrap().split("=");
+    temp_xs.next();
+    temp_xs = temp_xs.next().unwrap().split(",");
+    let mut xs = temp_xs.next().unwrap().split("..");
+
+    let mut temp_ys = splits.next().unwrap().split("=");
+    temp_ys.next();
+    let mut ys = temp_ys.next().unwrap().split("..");
+
+    Ok(TargetArea {
+        xmin: xs.next().unwrap().parse()?,
+        xmax: xs.next().unwrap().parse()?,
+        ymin: ys.next().unwrap().parse()?,
+        ymax: ys.next().unwrap().parse()?,
+    })
+}
 
-        assert_eq!(expected, part_1_solution(target_area))
-    }
+pub struct Day17;
 
-    #[test]
-    fn example2_part_1() {
-        let target_area = read_day_17(P.example_path("_2")).unwrap();
-        let expected = 45;
+impl Solution for Day17 {
+    const PROBLEM: Problem = P;
+    type Parsed = TargetArea;
+    type OutputPt1 = isize;
+    type OutputPt2 = isize;
 
-        assert_eq!(expected, part_1_solution(target_area))
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        Ok(parse(data)?)
     }
 
-    #[test]
-    fn input_part_1() {
-        let target_area = read_day_17(P.input_path()).unwrap();
-        let expected = 2775;
-        assert_eq!(expected, part_1_solution(target_area))
+    fn pt1(target_area: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(target_area)
     }
 
-    #[test]
-    fn example_part_2() {
-        let target_area = read_day_17(P.example_path("_1")).unwrap();
-        let expected = 112;
-        assert_eq!(expected, part_2_solution(target_area))
+    fn pt2(target_area: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(target_area)
     }
+}
+
+impl Visualize for Day17 {
+    /// part 1, a frame per step of the highest shot into the target area
+    fn visualize(target_area: &Self::Parsed, emit: &mut dyn FnMut(Frame)) {
+        let Some((velocity, path)) = target_area
+            .candidate_velocities()
+            .filter_map(|vel| Some((vel, target_area.trajectory(vel)?)))
+            .max_by_key(|(_, path)| height(path))
+        else {
+            return;
+        };
+        let xs = path
+            .iter()
+            .map(|p| p.x)
+            .chain([0, target_area.xmin, target_area.xmax]);
+        let ys = path
+            .iter()
+            .map(|p| p.y)
+            .chain([0, target_area.ymin, target_area.ymax]);
+        let (x0, x1) = (xs.clone().min().unwrap(), xs.max().unwrap());
+        let (y0, y1) = (ys.clone().min().unwrap(), ys.max().unwrap());
+
+        let (width, height) = ((x1 - x0 + 1) as usize, (y1 - y0 + 1) as usize);
+        // the step each position is first reached on
+        let mut reached = HashMap::new();
+        for (i, &pos) in path.iter().enumerate() {
+            reached.entry(pos).or_insert(i + 1);
+        }
 
-    #[test]
-    fn input_part_2() {
-        let target_area = read_day_17(P.input_path()).unwrap();
-        let expected = 1566;
-        assert_eq!(expected, part_2_solution(target_area))
+        for step in 0..=path.len() {
+            let caption = format!("velocity {}, step {}", velocity, step);
+            emit(Frame::from_fn(caption, width, height, |(x, y)| {
+                // rows count down from the top of the flight
+                let pos = Point2::new(x0 + x as isize, y1 - y as isize);
+                if step > 0 && pos == path[step - 1] {
+                    Cell::new('#', Color::Red)
+                } else if reached.get(&pos).is_some_and(|&i| i <= step) {
+                    Cell::new('#', Color::Yellow)
+                } else if pos == Point2::ORIGIN {
+                    Cell::new('S', Color::Green)
+                } else if target_area.is_in_area(pos) {
+                    Cell::new('T', Color::Blue)
+                } else {
+                    Cell::EMPTY
+                }
+            }));
+        }
     }
 }
+
+crate::examples!(Day17, pt1: [_1, _2], pt2: [_1]);
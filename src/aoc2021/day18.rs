@@ -1,7 +1,10 @@
 /// problem: https://adventofcode.com/2021/day/18
 /// input: "https://adventofcode.com/2021/day/18/input"
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2021,
     day: 18,
     name: "Snailfish",
@@ -28,32 +31,39 @@ pub enum Exploded {
     Me((usize, usize)),
     None, // exploded and used values
 }
-impl SnailFishNumber {
-    fn from_chars(chars: &mut Chars) -> anyhow::Result<Self> {
-        // left
-        let left = match chars.next().expect("unexpected end of string") {
-            '[' => {
-                // new snailfishnumber
-                SnailFish::Nested(Box::new(SnailFishNumber::from_chars(chars)?))
-            }
-            token if token.is_ascii_digit() => SnailFish::Val(token.to_digit(10).unwrap() as usize),
-            c => panic!("un expected char {c}"),
-        };
+impl SnailFish {
+    fn from_chars(chars: &mut Chars) -> Result<Self, ParseError> {
+        match chars.next().ok_or(ParseError::UnexpectedEnd)? {
+            // new snailfishnumber
+            '[' => Ok(SnailFish::Nested(Box::new(SnailFishNumber::from_chars(
+                chars,
+            )?))),
+            token => token
+                .to_digit(10)
+                .map(|v| SnailFish::Val(v as usize))
+                .ok_or(ParseError::UnexpectedChar(token)),
+        }
+    }
+}
 
-        // this token MUST be a comma
-        assert_eq!(',', chars.next().expect("expecting a comma"));
+/// consumes `expected` off the front of `chars`
+fn expect(chars: &mut Chars, expected: char) -> Result<(), ParseError> {
+    match chars.next() {
+        Some(c) if c == expected => Ok(()),
+        Some(found) => Err(ParseError::Expected { expected, found }),
+        None => Err(ParseError::UnexpectedEnd),
+    }
+}
 
-        // right
-        let right = match chars.next().expect("unexpected end of string") {
-            '[' => {
-                // new snailfishnumber
-                SnailFish::Nested(Box::new(SnailFishNumber::from_chars(chars)?))
-            }
-            token if token.is_ascii_digit() => SnailFish::Val(token.to_digit(10).unwrap() as usize),
-            c => panic!("un expected char {c}"),
-        };
+impl SnailFishNumber {
+    /// parses the rest of a pair whose opening `[` was already consumed
+    fn from_chars(chars: &mut Chars) -> Result<Self, ParseError> {
+        let left = SnailFish::from_chars(chars)?;
+        // this token MUST be a comma
+        expect(chars, ',')?;
+        let right = SnailFish::from_chars(chars)?;
         // this must be a ]
-        assert_eq!(']', chars.next().expect("expecting a comma"));
+        expect(chars, ']')?;
         Ok(Self { left, right })
     }
 
@@ -253,13 +263,17 @@ impl ToString for SnailFishNumber {
 }
 
 impl FromStr for SnailFishNumber {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> anyhow::Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         // [[[[4,3],4],4],[7,[[8,4],9]]] // commas can count the number of nested snaiulfish
-        let mut chars = s.chars();
-        chars.next();
-        SnailFishNumber::from_chars(&mut chars)
+        let mut chars = s.trim().chars();
+        expect(&mut chars, '[')?;
+        let number = SnailFishNumber::from_chars(&mut chars)?;
+        match chars.next() {
+            None => Ok(number),
+            Some(c) => Err(ParseError::UnexpectedChar(c)),
+        }
     }
 }
 
@@ -297,6 +311,33 @@ pub fn part_2_solution(snails: Vec<SnailFishNumber>) -> usize {
         .unwrap()
 }
 
+pub fn parse(input: &str) -> Result<Vec<SnailFishNumber>, Error> {
+    crate::parse_one_per_line(input)
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<SnailFishNumber>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
+    }
+
+    fn pt1(snails: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(snails.clone())
+    }
+
+    fn pt2(snails: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(snails.clone())
+    }
+}
+
+crate::examples!(Day18, pt1: [_1, _2, _3], pt2: [_3]);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -360,30 +401,21 @@ mod test {
     }
 
     #[test]
-    fn example3_part_1() {
-        let snails = read_to_one_per_line::<SnailFishNumber>(P.example_path("_3")).unwrap();
-        let expected = 4140;
-        assert_eq!(expected, part_1_solution(snails));
-    }
-
-    #[test]
-    fn input_part_1() {
-        let snails = read_to_one_per_line::<SnailFishNumber>(P.input_path()).unwrap();
-        let expected = 4365;
-        assert_eq!(expected, part_1_solution(snails));
-    }
-
-    #[test]
-    fn example_part_2() {
-        let snails = read_to_one_per_line::<SnailFishNumber>(P.example_path("_3")).unwrap();
-        let expected = 3993;
-        assert_eq!(expected, part_2_solution(snails));
-    }
-
-    #[test]
-    fn input_part_2() {
-        let snails = read_to_one_per_line::<SnailFishNumber>(P.input_path()).unwrap();
-        let expected = 4490;
-        assert_eq!(expected, part_2_solution(snails));
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(parse(&input).is_err());
+        let mut lines = input.lines().map(SnailFishNumber::from_str);
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::Expected {
+                expected: ',',
+                found: 'x'
+            }))
+        ));
+        assert!(matches!(lines.next(), Some(Err(ParseError::UnexpectedEnd))));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedChar('a')))
+        ));
     }
 }
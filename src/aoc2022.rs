@@ -11,5 +11,28 @@ pub mod day10;
 pub mod day11;
 pub mod day12;
 pub mod day13;
-pub mod day14;
-pub mod day15;
+pub mod day14;
+pub mod day15;
+
+use crate::DynSolution;
+
+/// every solved day of the year
+pub fn solutions() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        Box::new(day01::Day01),
+        Box::new(day02::Day02),
+        Box::new(day03::Day03),
+        Box::new(day04::Day04),
+        Box::new(day05::Day05),
+        Box::new(day06::Day06),
+        Box::new(day07::Day07),
+        Box::new(day08::Day08),
+        Box::new(day09::Day09),
+        Box::new(day10::Day10),
+        Box::new(day11::Day11),
+        Box::new(day12::Day12),
+        Box::new(day13::Day13),
+        Box::new(day14::Day14),
+        Box::new(day15::Day15),
+    ]
+}
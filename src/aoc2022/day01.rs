@@ -1,67 +1,58 @@
 /// problem: https://adventofcode.com/2022/day/1
 /// input: https://adventofcode.com/2022/day/1/input
-use crate::Problem;
+use crate::{error::Error, Problem, Solution};
 
-const P: Problem = Problem {
+pub const P: Problem = Problem {
     year: 2022,
     day: 1,
     name: "Calorie Counting",
 };
 
-fn pt1(data: &str) -> usize {
-    let elves = parse(data);
+fn pt1(elves: &[usize]) -> usize {
     match elves.iter().max() {
         Some(v) => *v,
         None => unreachable!(),
     }
 }
 
-fn pt2(data: &str) -> usize {
-    let mut elves = parse(data);
+fn pt2(elves: &[usize]) -> usize {
+    let mut elves = elves.to_vec();
     elves.sort();
     elves.iter().rev().take(3).sum()
 }
 
-fn parse(data: &str) -> Vec<usize> {
-    data.replace('\r', "")
-        .split("\n\n")
-        .map(|chunk| chunk.lines().map(|l| l.parse::<usize>().unwrap()).sum())
+fn parse(data: &str) -> Result<Vec<usize>, Error> {
+    crate::parse_chunks::<String>(data)?
+        .iter()
+        .map(|chunk| Ok(crate::parse_one_per_line::<usize>(chunk)?.iter().sum()))
         .collect()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::fs::read_to_string;
+pub struct Day01;
 
-    #[test]
-    fn pt1_example() {
-        let data = read_to_string(P.example_path("_1")).unwrap();
-        let expected = 24000;
-        assert_eq!(pt1(&data), expected);
-    }
+impl Solution for Day01 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<usize>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    #[test]
-    fn pt1_input() {
-        let data = read_to_string(P.input_path()).unwrap();
-        let expected = 67633;
-
-        assert_eq!(pt1(&data), expected);
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
-    #[test]
-    fn pt2_example() {
-        let data = read_to_string(P.example_path("_1")).unwrap();
-        let expected = 45000;
-        assert_eq!(pt2(&data), expected);
+
+    fn pt1(elves: &Self::Parsed) -> Self::OutputPt1 {
+        pt1(elves)
     }
 
-    #[test]
-    fn pt2_input() {
-        let data = read_to_string(P.input_path()).unwrap();
-        let expected = 199628;
-        assert_eq!(pt2(&data), expected);
+    fn pt2(elves: &Self::Parsed) -> Self::OutputPt2 {
+        pt2(elves)
     }
+}
 
+crate::examples!(Day01, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
     #[test]
     pub fn test_string() {
         let mut s = "apple".chars(); //  ['a' 'p' 'p' 'l' 'e']
@@ -2,8 +2,11 @@
 /// input: https://adventofcode.com/2022/day/2/input
 use std::str::FromStr;
 
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 2,
     name: "Rock Paper Scissors",
@@ -59,93 +62,112 @@ impl Game {
     }
 
     /// TODO: i don't like this 
-    pub fn from_str_part1(s: &str) -> anyhow::Result<Self> {
-        let (l, r) = s.trim().split_once(' ').expect("should be have space");
-        let p1 = match l {
-            "A" => Hand::Rock,
-            "B" => Hand::Paper,
-            "C" => Hand::Scissors,
-            _ => panic!("shouldn't be other char here"),
-        };
+    pub fn from_str_part1(s: &str) -> Result<Self, ParseError> {
+        let (p1, r) = columns(s)?;
         let p2 = match r {
             "X" => Hand::Rock,
             "Y" => Hand::Paper,
             "Z" => Hand::Scissors,
-            _ => panic!("shouldn't be other char here"),
+            _ => return Err(ParseError::UnexpectedToken(r.to_owned())),
         };
         Ok(Self { p1, p2 })
     }
 }
 
 impl FromStr for Game {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> anyhow::Result<Self> {
-        let (l, r) = s.trim().split_once(' ').expect("should be have space");
-        let p1 = match l {
-            "A" => Hand::Rock,
-            "B" => Hand::Paper,
-            "C" => Hand::Scissors,
-            _ => panic!("shouldn't be other char here"),
-        };
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (p1, r) = columns(s)?;
         let p2 = match r {
             "X" => p1.defeats(),
             "Y" => p1.draws(),
             "Z" => p1.loses_to(),
-            _ => panic!("shouldn't be other char here"),
+            _ => return Err(ParseError::UnexpectedToken(r.to_owned())),
         };
         Ok(Self { p1, p2 })
     }
 }
 
-pub fn part_1_solution(games: Vec<Game>) -> usize {
+/// the opponent's hand and the second column of a round
+fn columns(s: &str) -> Result<(Hand, &str), ParseError> {
+    let (l, r) = s
+        .trim()
+        .split_once(' ')
+        .ok_or_else(|| ParseError::UnexpectedToken(s.to_owned()))?;
+    let p1 = match l {
+        "A" => Hand::Rock,
+        "B" => Hand::Paper,
+        "C" => Hand::Scissors,
+        _ => return Err(ParseError::UnexpectedToken(l.to_owned())),
+    };
+    Ok((p1, r))
+}
+
+pub fn part_1_solution(games: &[Game]) -> usize {
     games.iter().map(|g| g.evaluate()).sum()
 }
 
-pub fn part_2_solution(games: Vec<Game>) -> usize {
+pub fn part_2_solution(games: &[Game]) -> usize {
     games.iter().map(|g| g.evaluate()).sum()
 }
 
-#[cfg(test)]
-mod test {
-    use std::fs::read_to_string;
+pub struct Day02;
 
-    use super::*;
-    use crate::read_to_one_per_line;
+impl Solution for Day02 {
+    const PROBLEM: Problem = P;
+    type Parsed = (Vec<Game>, Vec<Game>);
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    #[test]
-    fn example_part_1() {
-        let games: Vec<Game> = read_to_string(P.example_path("_1"))
-            .expect("no such file")
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        // both parts read the same columns, so part 2 reports where a line is bad
+        let part_2 = crate::parse_one_per_line(data)?;
+        let part_1 = data
             .lines()
-            .map(|line| Game::from_str_part1(line).expect("should be a game"))
-            .collect();
-        let expected = 15;
-        assert_eq!(expected, part_1_solution(games))
+            .map(Game::from_str_part1)
+            .collect::<Result<_, _>>()?;
+        Ok((part_1, part_2))
     }
 
-    #[test]
-    fn input_part_1() {
-        let games: Vec<Game> = read_to_string(P.input_path())
-            .expect("no such file")
-            .lines()
-            .map(|line| Game::from_str_part1(line).expect("should be a game"))
-            .collect();
-        let expected = 12855;
-        assert_eq!(expected, part_1_solution(games))
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(&parsed.0)
     }
 
-    #[test]
-    fn example_part_2() {
-        let games = read_to_one_per_line::<Game>(P.example_path("_1")).unwrap();
-        let expected = 12;
-        assert_eq!(expected, part_2_solution(games))
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(&parsed.1)
     }
+}
+
+crate::examples!(Day02, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
     #[test]
-    fn input_part_2() {
-        let games = read_to_one_per_line::<Game>(P.input_path()).unwrap();
-        let expected = 13726;
-        assert_eq!(expected, part_2_solution(games))
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(matches!(
+            Day02::parse(&input),
+            Err(Error::ParseError(ParseError::Located { location, .. })) if location.line == 2
+        ));
+        let mut lines = input.lines().skip(1).map(Game::from_str);
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedToken(t))) if t == "B"
+        ));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedToken(t))) if t == "W"
+        ));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedToken(t))) if t == "D"
+        ));
+        assert!(matches!(
+            Game::from_str_part1("C W"),
+            Err(ParseError::UnexpectedToken(t)) if t == "W"
+        ));
     }
 }
@@ -1,7 +1,7 @@
 /// problem: https://adventofcode.com/2022/day/3
 /// input: https://adventofcode.com/2022/day/3/input
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{error::Error, Problem, Solution};
+pub const P: Problem = Problem {
     year: 2022,
     day: 3,
     name: "RuckSacks",
@@ -49,10 +49,10 @@ impl RuckSack {
     }
 }
 
-pub fn part_1_solution(rucksacks: Vec<RuckSack>) -> usize {
+pub fn part_1_solution(rucksacks: &[RuckSack]) -> usize {
     rucksacks.iter().map(|rs| rs.score()).sum()
 }
-pub fn part_2_solution(rucksacks: Vec<String>) -> usize {
+pub fn part_2_solution(rucksacks: &[String]) -> usize {
     let mut score = 0;
     for chunk in rucksacks.chunks(3) {
         let mut hs = HashSet::new();
@@ -75,36 +75,30 @@ pub fn part_2_solution(rucksacks: Vec<String>) -> usize {
     score
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::read_to_one_per_line;
+pub struct Day03;
 
-    #[test]
-    fn example_part_1() {
-        let input = read_to_one_per_line::<RuckSack>(P.example_path("_1")).unwrap();
-        let expected = 157;
-        assert_eq!(expected, part_1_solution(input))
-    }
+impl Solution for Day03 {
+    const PROBLEM: Problem = P;
+    type Parsed = (Vec<RuckSack>, Vec<String>);
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    #[test]
-    fn input_part_1() {
-        let input = read_to_one_per_line::<RuckSack>(P.input_path()).unwrap();
-        let expected = 7990;
-        assert_eq!(expected, part_1_solution(input))
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        let rucksacks = data
+            .lines()
+            .map(|line| line.parse())
+            .collect::<anyhow::Result<_>>()?;
+        let lines = data.lines().map(|line| line.to_owned()).collect();
+        Ok((rucksacks, lines))
     }
 
-    #[test]
-    fn example_part_2() {
-        let input = read_to_one_per_line::<String>(P.example_path("_1")).unwrap();
-        let expected = 70;
-        assert_eq!(expected, part_2_solution(input))
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(&parsed.0)
     }
 
-    #[test]
-    fn input_part_2() {
-        let input = read_to_one_per_line::<String>(P.input_path()).unwrap();
-        let expected = 2602;
-        assert_eq!(expected, part_2_solution(input))
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(&parsed.1)
     }
 }
+
+crate::examples!(Day03, pt1: [_1], pt2: [_1]);
@@ -1,104 +1,80 @@
 /// problem: https://adventofcode.com/2022/day/4
 /// input: https://adventofcode.com/2022/day/4/input
-use std::{ops::RangeInclusive, str::FromStr};
+use std::str::FromStr;
 
-use itertools::Itertools;
-
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    range_set::RangeSet,
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 4,
     name: "Elf Ranges",
 };
 
-#[repr(transparent)]
-pub struct ElfRange(RangeInclusive<usize>);
-
-impl ElfRange {
-    fn contains_elfrange(&self, other: &Self) -> bool {
-        // TODO how to auto destructure tuple struct
-        self.0.contains(other.0.start()) && self.0.contains(other.0.end()) // O(1) just checks bounds
-    }
-
-    fn overlaps_elfrange(&self, other: &Self) -> bool {
-        // TODO how to auto destructure tuple struct
-        self.0.contains(other.0.start()) || self.0.contains(other.0.end())
-    }
-}
-
 pub struct Pair {
-    left: ElfRange,
-    right: ElfRange,
+    left: RangeSet<usize>,
+    right: RangeSet<usize>,
 }
 
 impl Pair {
     fn is_contained(&self) -> bool {
-        self.left.contains_elfrange(&self.right) || self.right.contains_elfrange(&self.left)
+        self.left.is_subset(&self.right) || self.right.is_subset(&self.left)
     }
 
     fn is_overlapped(&self) -> bool {
-        self.left.overlaps_elfrange(&self.right) || self.right.overlaps_elfrange(&self.left)
+        !self.left.intersection(&self.right).is_empty()
     }
 }
 impl FromStr for Pair {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> anyhow::Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         // 2-4,6-8
-        let (left, right) = s
-            .split(',')
-            .map(|range| {
-                range
-                    .split('-')
-                    .map(|n| n.parse().expect("Should be usize"))
-                    .collect_tuple::<(usize, usize)>()
-                    .map(|(low, high)| ElfRange(low..=high))
-                    .expect("must be 2")
-            })
-            .collect_tuple::<(_, _)>()
-            .expect("must be 2");
-
-        Ok(Pair { left, right })
+        let range = |range: &str| -> Result<RangeSet<usize>, ParseError> {
+            let (low, high) = range.split_once('-').ok_or(ParseError::UnexpectedEnd)?;
+            Ok(RangeSet::from(low.parse()?..=high.parse()?))
+        };
+        let (left, right) = s.split_once(',').ok_or(ParseError::UnexpectedEnd)?;
+
+        Ok(Pair {
+            left: range(left)?,
+            right: range(right)?,
+        })
     }
 }
 
-pub fn part_1_solution(pairs: Vec<Pair>) -> usize {
+pub fn part_1_solution(pairs: &[Pair]) -> usize {
     pairs.iter().filter(|&pair| pair.is_contained()).count()
 }
-pub fn part_2_solution(pairs: Vec<Pair>) -> usize {
+pub fn part_2_solution(pairs: &[Pair]) -> usize {
     pairs.iter().filter(|&pair| pair.is_overlapped()).count()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::read_to_one_per_line;
+pub fn parse(input: &str) -> Result<Vec<Pair>, Error> {
+    crate::parse_one_per_line(input)
+}
+
+pub struct Day04;
 
-    #[test]
-    fn example_part_1() {
-        let pairs = read_to_one_per_line::<Pair>(P.example_path("_1")).unwrap();
-        let expected = 2;
-        assert_eq!(expected, part_1_solution(pairs))
-    }
+impl Solution for Day04 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<Pair>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    #[test]
-    fn input_part_1() {
-        let pairs = read_to_one_per_line::<Pair>(P.input_path()).unwrap();
-        let expected = 444;
-        assert_eq!(expected, part_1_solution(pairs))
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn example_part_2() {
-        let pairs = read_to_one_per_line::<Pair>(P.example_path("_1")).unwrap();
-        let expected = 4;
-        assert_eq!(expected, part_2_solution(pairs))
+    fn pt1(pairs: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(pairs)
     }
 
-    #[test]
-    fn input_part_2() {
-        let pairs = read_to_one_per_line::<Pair>(P.input_path()).unwrap();
-        let expected = 801;
-        assert_eq!(expected, part_2_solution(pairs))
+    fn pt2(pairs: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(pairs)
     }
 }
+
+crate::examples!(Day04, pt1: [_1], pt2: [_1]);
@@ -5,8 +5,11 @@ use std::{
     str::{FromStr, Lines},
 };
 
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 5,
     name: "Elf Stacks",
@@ -14,31 +17,36 @@ const P: Problem = Problem {
 
 /*
 */
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Action {
     count: usize,
     from: usize,
     to: usize,
 }
 impl FromStr for Action {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> anyhow::Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         //move 1 from 2 to 1
         let mut splits = s.split(' ');
-        splits.next();
-        let count = splits.next().unwrap().parse::<usize>().unwrap();
-        splits.next();
-        let from = splits.next().unwrap().parse::<usize>().unwrap();
-        splits.next();
-        let to = splits.next().unwrap().parse::<usize>().unwrap();
+        let mut number = |word: &str| -> Result<usize, ParseError> {
+            match splits.next() {
+                Some(w) if w == word => {}
+                Some(w) => return Err(ParseError::UnexpectedToken(w.to_owned())),
+                None => return Err(ParseError::UnexpectedEnd),
+            }
+            Ok(splits.next().ok_or(ParseError::UnexpectedEnd)?.parse()?)
+        };
+        let count = number("move")?;
+        let from = number("from")?;
+        let to = number("to")?;
         Ok(Action { count, from, to })
     }
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Stacks(Vec<Vec<char>>);
 impl Stacks {
-    pub fn from_lines(lines: &mut Lines) -> Self {
+    pub fn from_lines(lines: &mut Lines) -> Result<Self, ParseError> {
         // [W] [V]     [P]
         // [B] [T]     [C] [B]     [G]
         // [G] [S]     [V] [H] [N] [T]
@@ -55,7 +63,10 @@ impl Stacks {
             // this will move the &mut lines
             let mut cs = ln.chars();
             cs.next();
-            if cs.next().expect("the blank row should be skipped") == '1' {
+            let second = cs
+                .next()
+                .ok_or_else(|| ParseError::UnexpectedToken(ln.to_owned()))?;
+            if second == '1' {
                 break;
             }
 
@@ -63,8 +74,9 @@ impl Stacks {
                 .chars() // [W] [V]     [P]
                 .collect::<Vec<char>>() //  ^   ^   ^   ^
                 .chunks(4) // 0123012301230123
-                .map(|chunk| chunk[1]) // the target value is at ind 1
-                .collect::<Vec<char>>(); // W,V, ,P
+                .map(|chunk| chunk.get(1).copied()) // the target value is at ind 1
+                .collect::<Option<Vec<char>>>() // W,V, ,P
+                .ok_or_else(|| ParseError::UnexpectedToken(ln.to_owned()))?;
 
             for (stack_ind, char) in cleaned.iter().enumerate() {
                 match char {
@@ -82,7 +94,7 @@ impl Stacks {
         for v in vs.iter_mut() {
             v.reverse();
         }
-        Self(vs)
+        Ok(Self(vs))
     }
 }
 
@@ -122,6 +134,55 @@ pub fn part_2_solution(mut stacks: Stacks, actions: Vec<Action>) -> String {
         .collect::<String>()
 }
 
+pub fn parse(input: &str) -> Result<(Stacks, Vec<Action>), Error> {
+    let mut lines = input.lines();
+
+    let stacks = Stacks::from_lines(&mut lines)?;
+    match lines.next() {
+        Some("") => {}
+        Some(ln) => return Err(ParseError::UnexpectedToken(ln.to_owned()).into()),
+        None => return Err(ParseError::UnexpectedEnd.into()),
+    }
+
+    let mut actions = Vec::new();
+    for line in lines {
+        let action = Action::from_str(line)?;
+        let on_stacks = 1..=stacks.0.len();
+        if !on_stacks.contains(&action.from) || !on_stacks.contains(&action.to) {
+            return Err(
+                ParseError::InvalidSequence("a move between stacks that aren't there").into(),
+            );
+        }
+        actions.push(action);
+    }
+    Ok((stacks, actions))
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const PROBLEM: Problem = P;
+    type Parsed = (Stacks, Vec<Action>);
+    type OutputPt1 = String;
+    type OutputPt2 = String;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
+    }
+
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        let (stacks, actions) = parsed;
+        part_1_solution(stacks.clone(), actions.clone())
+    }
+
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        let (stacks, actions) = parsed;
+        part_2_solution(stacks.clone(), actions.clone())
+    }
+}
+
+crate::examples!(Day05, pt1: [_1], pt2: [_1]);
+
 #[cfg(test)]
 #[allow(unused)]
 mod test {
@@ -131,20 +192,31 @@ mod test {
     use std::{fs::read_to_string, path::Path};
 
     pub fn read_day_5(path: impl AsRef<Path>) -> (Stacks, Vec<Action>) {
-        let file = read_to_string(path).unwrap();
-        let mut lines = file.lines();
-
-        let stacks = Stacks::from_lines(&mut lines);
-        match lines.next() {
-            Some(ln) => assert!(ln.is_empty()),
-            None => unreachable!(),
-        }
+        parse(&read_to_string(path).unwrap()).unwrap()
+    }
 
-        let mut actions = Vec::new();
-        for line in lines {
-            actions.push(Action::from_str(line).unwrap());
-        }
-        (stacks, actions)
+    #[test]
+    fn malformed() {
+        let input = read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(parse(&input).is_err());
+        let mut lines = input.lines().skip(5).map(Action::from_str);
+        assert!(matches!(lines.next(), Some(Ok(_))));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::ParseIntError(_)))
+        ));
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ParseError::UnexpectedToken(w))) if w == "shift"
+        ));
+        assert!(matches!(lines.next(), Some(Err(ParseError::UnexpectedEnd))));
+
+        let missing_stack = input.replace("move x from 1 to 3", "move 1 from 4 to 1");
+        let missing_stack = missing_stack.lines().take(7).collect::<Vec<_>>().join("\n");
+        assert!(matches!(
+            parse(&missing_stack),
+            Err(Error::ParseError(ParseError::InvalidSequence(_)))
+        ));
     }
 
     // #[test]
@@ -152,34 +224,4 @@ mod test {
     //     let splits = read_to_chunks(P.get_example_path("_1")).unwrap();
     //     println!("{:?}", splits);
     // }
-
-    #[test]
-    fn example_part_1() {
-        let stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
-        let (stacks, actions) = read_day_5(P.example_path("_1"));
-
-        let expected = "CMZ".to_owned();
-        assert_eq!(expected, part_1_solution(stacks, actions))
-    }
-
-    #[test]
-    fn input_part_1() {
-        let (stacks, actions) = read_day_5(P.input_path());
-        let expected = "TBVFVDZPN".to_owned();
-        assert_eq!(expected, part_1_solution(stacks, actions))
-    }
-
-    #[test]
-    fn example_part_2() {
-        let (stacks, actions) = read_day_5(P.example_path("_1"));
-        let expected = "MCD".to_owned();
-        assert_eq!(expected, part_2_solution(stacks, actions))
-    }
-
-    #[test]
-    fn input_part_2() {
-        let (stacks, actions) = read_day_5(P.input_path());
-        let expected = "VLCWHTDSZ".to_owned();
-        assert_eq!(expected, part_2_solution(stacks, actions))
-    }
 }
@@ -3,8 +3,11 @@
 ///
 use std::{collections::HashMap, str::FromStr};
 
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 7,
     name: "No Space Left On Device",
@@ -24,23 +27,24 @@ pub enum Command {
 }
 
 impl FromStr for Command {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> anyhow::Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut splits = s.split(' ');
-        let res = match splits.next().unwrap() {
+        let mut next = || splits.next().ok_or(ParseError::UnexpectedEnd);
+        let res = match next()? {
             "$" => {
                 // cmd
-                match splits.next().unwrap() {
+                match next()? {
                     "ls" => Command::ListDir,
-                    "cd" => Command::ChangeDir(splits.next().unwrap().to_owned()),
-                    _ => panic!(),
+                    "cd" => Command::ChangeDir(next()?.to_owned()),
+                    cmd => return Err(ParseError::UnexpectedToken(cmd.to_owned())),
                 }
             }
-            "dir" => Command::MakeDir(splits.next().unwrap().to_owned()),
+            "dir" => Command::MakeDir(next()?.to_owned()),
             size => Command::MakeFile(File {
                 size: size.parse()?,
-                _name: splits.next().unwrap().to_owned(),
+                _name: next()?.to_owned(),
             }),
         };
 
@@ -48,7 +52,8 @@ impl FromStr for Command {
     }
 }
 
-pub fn part_1_solution(commands: Vec<Command>) -> usize {
+/// total size of every directory, keyed by its path
+pub fn directory_sizes(commands: &[Command]) -> HashMap<Vec<String>, usize> {
     let mut paths: HashMap<Vec<String>, usize> = HashMap::new();
     let mut current_path: Vec<String> = Vec::new();
     for command in commands {
@@ -57,7 +62,7 @@ pub fn part_1_solution(commands: Vec<Command>) -> usize {
                 if dir == ".." {
                     current_path.pop();
                 } else {
-                    current_path.push(dir);
+                    current_path.push(dir.to_owned());
                 }
             }
             Command::ListDir => {}
@@ -72,77 +77,90 @@ pub fn part_1_solution(commands: Vec<Command>) -> usize {
         }
     }
     paths
-        .values()
-        .into_iter()
-        .map(|size| if size <= &100000 { size } else { &0 })
-        .sum()
 }
-pub fn part_2_solution(commands: Vec<Command>) -> usize {
-    let mut paths: HashMap<Vec<String>, usize> = HashMap::new();
-    let mut current_path: Vec<String> = Vec::new();
-    for command in commands {
-        match command {
-            Command::ChangeDir(dir) => {
-                if dir == ".." {
-                    current_path.pop();
-                } else {
-                    current_path.push(dir);
-                }
-            }
-            Command::ListDir => {}
-            Command::MakeFile(file) => {
-                let mut cp = current_path.clone();
-                while !cp.is_empty() {
-                    *paths.entry(cp.clone()).or_default() += file.size;
-                    cp.pop();
-                }
-            }
-            Command::MakeDir(_) => {}
-        }
-    }
-    let target_space = 30000000;
-    let total_allocation = 70000000;
+
+pub fn part_1_solution(paths: &HashMap<Vec<String>, usize>) -> usize {
+    paths.values().filter(|&&size| size <= 100000).sum()
+}
+pub fn part_2_solution(paths: &HashMap<Vec<String>, usize>) -> usize {
+    let target_space: usize = 30000000;
+    let total_allocation: usize = 70000000;
     let home = vec!["/".to_owned()];
-    let total_used = paths.get(&home).unwrap();
-    let required_additional_space = target_space - (total_allocation - total_used);
-    *paths
+    let total_used = paths.get(&home).copied().unwrap_or_default();
+    let free_space = total_allocation.saturating_sub(total_used);
+    let required_additional_space = target_space.saturating_sub(free_space);
+    paths
         .values()
-        .into_iter()
-        .filter(|&&size| size >= required_additional_space)
+        .copied()
+        .filter(|&size| size >= required_additional_space)
         .min()
-        .unwrap()
+        .unwrap_or_default()
 }
 
+/// the commands, which must start from the root directory so every path is rooted at "/"
+pub fn parse(input: &str) -> Result<Vec<Command>, Error> {
+    let commands: Vec<Command> = crate::parse_one_per_line(input)?;
+    match commands.first() {
+        Some(Command::ChangeDir(dir)) if dir == "/" => Ok(commands),
+        _ => Err(ParseError::InvalidSequence("the first command isn't `$ cd /`").into()),
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const PROBLEM: Problem = P;
+    type Parsed = HashMap<Vec<String>, usize>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        Ok(directory_sizes(&parse(data)?))
+    }
+
+    fn pt1(paths: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(paths)
+    }
+
+    fn pt2(paths: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(paths)
+    }
+}
+
+crate::examples!(Day07, pt1: [_1], pt2: [_1]);
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::read_to_one_per_line;
-
-    #[test]
-    fn example_part_1() {
-        let commands = read_to_one_per_line::<Command>(P.example_path("_1")).unwrap();
-        let expected = 95437;
-        assert_eq!(expected, part_1_solution(commands))
-    }
 
     #[test]
-    fn input_part_1() {
-        let commands = read_to_one_per_line::<Command>(P.input_path()).unwrap();
-        let expected = 2104783;
-        assert_eq!(expected, part_1_solution(commands))
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(parse(&input).is_err());
+        let unknown = input.lines().nth(1).unwrap();
+        assert!(matches!(
+            unknown.parse::<Command>(),
+            Err(ParseError::UnexpectedToken(cmd)) if cmd == "rm"
+        ));
+        assert!(matches!(
+            "$".parse::<Command>(),
+            Err(ParseError::UnexpectedEnd)
+        ));
     }
 
     #[test]
-    fn example_part_2() {
-        let commands = read_to_one_per_line::<Command>(P.example_path("_1")).unwrap();
-        let expected = 24933642;
-        assert_eq!(expected, part_2_solution(commands))
+    fn unrooted() {
+        assert!(matches!(
+            parse("$ ls\n14848514 b.txt"),
+            Err(Error::ParseError(ParseError::InvalidSequence(_)))
+        ));
     }
 
     #[test]
-    fn input_part_2() {
-        let commands = read_to_one_per_line::<Command>(P.input_path()).unwrap();
-        let expected = 5883165;
-        assert_eq!(expected, part_2_solution(commands))
+    fn full_disk() {
+        let paths = directory_sizes(&parse("$ cd /\n$ ls\n80000000 a.txt").unwrap());
+        assert_eq!(part_2_solution(&paths), 80000000);
+        let paths = directory_sizes(&parse("$ cd /\n$ ls\ndir a").unwrap());
+        assert_eq!(part_2_solution(&paths), 0);
     }
 }
@@ -1,186 +1,79 @@
 /// problem: https://adventofcode.com/2022/day/8
 /// input: https://adventofcode.com/2022/day/8/input
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{error::Error, Problem, Solution};
+pub const P: Problem = Problem {
     year: 2022,
     day: 8,
     name: "Treetop Tree House",
 };
-fn get_externally_visible(tree_heights: &Vec<Vec<usize>>) -> Vec<Vec<bool>> {
-    let row_count = tree_heights.len();
-    let col_count = tree_heights[0].len();
-    let mut visibilities = vec![vec![false; col_count]; row_count];
-
-    let max_row = row_count - 1;
-    let max_col = col_count - 1;
-    for row in 0..row_count {
-        for col in 0..col_count {
-            // north
-            let mut is_visible = row == 0 || col == 0 || row == max_row || col == max_col;
-            let h = tree_heights[row][col];
-
-            let mut row_temp = row;
-            while row_temp > 0 {
-                row_temp -= 1;
-                if tree_heights[row_temp][col] >= h {
-                    break;
-                }
-                if row_temp == 0 {
-                    is_visible = true;
-                }
-            }
-
-            let mut row_temp = row;
-            while row_temp < max_row {
-                row_temp += 1;
-                if tree_heights[row_temp][col] >= h {
-                    break;
-                }
-                if row_temp == max_row {
-                    is_visible = true;
-                }
-            }
-
-            let mut col_temp = col;
-            while col_temp > 0 {
-                col_temp -= 1;
-                if tree_heights[row][col_temp] >= h {
-                    break;
-                }
-                if col_temp == 0 {
-                    is_visible = true;
-                }
-            }
-
-            let mut col_temp = col;
-            while col_temp < max_col {
-                col_temp += 1;
-                if tree_heights[row][col_temp] >= h {
-                    break;
-                }
-                if col_temp == max_col {
-                    is_visible = true;
-                }
-            }
-            visibilities[row][col] = is_visible;
-        }
-    }
-    visibilities
+use crate::grid::{Grid, Pos, ORTHOGONAL};
+
+/// a tree is visible if every tree between it and an edge is shorter
+fn is_externally_visible(tree_heights: &Grid<usize>, tree: Pos) -> bool {
+    let h = tree_heights[tree];
+    ORTHOGONAL.iter().any(|&d| {
+        tree_heights
+            .ray(tree, d)
+            .all(|other| tree_heights[other] < h)
+    })
 }
 
-fn get_scenic_scores(tree_heights: &Vec<Vec<usize>>) -> Vec<Vec<usize>> {
-    let row_count = tree_heights.len();
-    let col_count = tree_heights[0].len();
-    let mut scores = vec![vec![0; col_count]; row_count];
-
-    for row in 0..row_count {
-        for col in 0..col_count {
-            // north
-            let mut score = 1;
-            let h = tree_heights[row][col];
-
-            let mut row_temp = row;
-            let mut score_temp = 0;
-            while row_temp > 0 {
-                row_temp -= 1;
-                score_temp += 1;
-                if tree_heights[row_temp][col] >= h {
-                    break;
-                }
-            }
-            score *= score_temp;
-
-            let mut row_temp = row;
-            let mut score_temp = 0;
-            while row_temp < row_count - 1 {
-                row_temp += 1;
-                score_temp += 1;
-                if tree_heights[row_temp][col] >= h {
+/// the product of how far you can see in each direction, up to and including the first blocking tree
+fn scenic_score(tree_heights: &Grid<usize>, tree: Pos) -> usize {
+    let h = tree_heights[tree];
+    ORTHOGONAL
+        .iter()
+        .map(|&d| {
+            let mut score = 0;
+            for other in tree_heights.ray(tree, d) {
+                score += 1;
+                if tree_heights[other] >= h {
                     break;
                 }
             }
-            score *= score_temp;
-
-            let mut col_temp = col;
-            let mut score_temp = 0;
-            while col_temp > 0 {
-                col_temp -= 1;
-                score_temp += 1;
-                if tree_heights[row][col_temp] >= h {
-                    break;
-                }
-            }
-            score *= score_temp;
+            score
+        })
+        .product()
+}
 
-            let mut col_temp = col;
-            let mut score_temp = 0;
-            while col_temp < col_count - 1 {
-                col_temp += 1;
-                score_temp += 1;
-                if tree_heights[row][col_temp] >= h {
-                    break;
-                }
-            }
-            score *= score_temp;
-            scores[row][col] = score;
-        }
-    }
-    scores
+pub fn part_1_solution(tree_heights: &Grid<usize>) -> usize {
+    tree_heights
+        .positions()
+        .filter(|&tree| is_externally_visible(tree_heights, tree))
+        .count()
 }
 
-pub fn part_1_solution(tree_heights: Vec<Vec<usize>>) -> usize {
-    // initialize the visited
-    let visibility = get_externally_visible(&tree_heights);
-    visibility.iter().flatten().filter(|&f| *f).count()
+pub fn part_2_solution(tree_heights: &Grid<usize>) -> usize {
+    tree_heights
+        .positions()
+        .map(|tree| scenic_score(tree_heights, tree))
+        .max()
+        .unwrap()
 }
 
-pub fn part_2_solution(tree_heights: Vec<Vec<usize>>) -> usize {
-    let scores = get_scenic_scores(&tree_heights);
-    *scores.iter().flatten().max().unwrap()
+pub fn parse(input: &str) -> Result<Grid<usize>, Error> {
+    Grid::parse_with(input, |c| c.to_digit(10).map(|d| d as usize))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::read_to_vec_per_line;
+pub struct Day08;
 
-    #[test]
-    fn example_part_1() {
-        let input = read_to_vec_per_line(P.example_path("_1"), |c| {
-            Some(c.to_digit(10).expect("should be usize") as usize)
-        })
-        .expect("unable to open file");
-        let expected = 21;
-        assert_eq!(expected, part_1_solution(input))
-    }
+impl Solution for Day08 {
+    const PROBLEM: Problem = P;
+    type Parsed = Grid<usize>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    #[test]
-    fn input_part_1() {
-        let input = read_to_vec_per_line(P.input_path(), |c| {
-            Some(c.to_digit(10).expect("should be usize") as usize)
-        })
-        .expect("unable to open file");
-        let expected = 1807;
-        assert_eq!(expected, part_1_solution(input))
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn example_part_2() {
-        let input = read_to_vec_per_line(P.example_path("_1"), |c| {
-            Some(c.to_digit(10).expect("should be usize") as usize)
-        })
-        .expect("unable to open file");
-        let expected = 8;
-        assert_eq!(expected, part_2_solution(input))
+    fn pt1(tree_heights: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(tree_heights)
     }
 
-    #[test]
-    fn input_part_2() {
-        let input = read_to_vec_per_line(P.input_path(), |c| {
-            Some(c.to_digit(10).expect("should be usize") as usize)
-        })
-        .expect("unable to open file");
-        let expected = 480000;
-        assert_eq!(expected, part_2_solution(input))
+    fn pt2(tree_heights: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(tree_heights)
     }
 }
+
+crate::examples!(Day08, pt1: [_1], pt2: [_1]);
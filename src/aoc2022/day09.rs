@@ -3,87 +3,61 @@
 ///
 use std::{collections::HashSet, str::FromStr};
 
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    geom::Point2,
+    visualize::{Cell, Color, Frame, Visualize},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 9,
     name: "Rope Bridge",
 };
 
 pub struct Instruction {
-    dir: Coord,
+    dir: Point2<isize>,
     steps: usize,
 }
 
 impl FromStr for Instruction {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> anyhow::Result<Self> {
-        let mut cs = s.split(' ');
-        let dir = match cs.next().unwrap() {
-            "R" => Coord(0, 1),
-            "U" => Coord(1, 0),
-            "L" => Coord(0, -1),
-            "D" => Coord(-1, 0),
-            _ => panic!(),
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dir, steps) = s.split_once(' ').ok_or(ParseError::UnexpectedEnd)?;
+        let dir = match dir {
+            "R" => Point2::RIGHT,
+            "U" => Point2::UP,
+            "L" => Point2::LEFT,
+            "D" => Point2::DOWN,
+            dir => return Err(ParseError::UnexpectedToken(dir.to_owned())),
         };
-        let steps = cs.next().unwrap().parse().unwrap();
+        let steps = steps.parse()?;
         Ok(Instruction { dir, steps })
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Coord(isize, isize);
-impl std::ops::AddAssign for Coord {
-    fn add_assign(&mut self, rhs: Self) {
-        self.0 += rhs.0;
-        self.1 += rhs.1;
-    }
-}
-
-impl Coord {
-    pub fn length(&self) -> f32 {
-        ((self.0.pow(2) + self.1.pow(2)) as f32).sqrt()
-    }
-
-    pub fn direction(&self) -> Coord {
-        Coord(self.0.signum(), self.1.signum())
-    }
-}
-
-impl std::ops::Add for Coord {
-    type Output = Coord;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Coord(self.0 + rhs.0, self.1 + rhs.1)
-    }
-}
-
-impl std::ops::Sub for Coord {
-    type Output = Coord;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Coord(self.0 - rhs.0, self.1 - rhs.1)
+/// the tail stays put while it touches the head, diagonals included, otherwise it steps towards it
+pub fn update_tail(head: &Point2<isize>, tail: &Point2<isize>) -> Point2<isize> {
+    if head.chebyshev(*tail) <= 1 {
+        *tail
+    } else {
+        *tail + (*head - *tail).signum()
     }
 }
 
-pub fn update_tail(head: &Coord, tail: &Coord) -> Coord {
-    let head_vec = *head - *tail;
-    let length = head_vec.length();
-    let dir = head_vec.direction(); // dir is either  (1,0), (-1,0), (0,-1), (0,1),  (1,1), (-1,-1)
-    if length <= 2f32.sqrt() {
-        // could just do <2
-        // the vec is either (0,1) (1,0) (1,1) do nothing
-        *tail
-    } else {
-        *tail + dir
+/// steps the head in `dir`, with each knot after it following the one in front
+fn pull(knots: &mut [Point2<isize>], dir: Point2<isize>) {
+    knots[0] += dir;
+    for i in 1..knots.len() {
+        knots[i] = update_tail(&knots[i - 1], &knots[i]);
     }
 }
 
-pub fn part_1_solution(instructions: Vec<Instruction>) -> usize {
-    let mut visited: HashSet<Coord> = HashSet::new();
-    let mut tail = Coord(0, 0);
-    let mut head = Coord(0, 0);
+pub fn part_1_solution(instructions: &[Instruction]) -> usize {
+    let mut visited: HashSet<Point2<isize>> = HashSet::new();
+    let mut tail = Point2::ORIGIN;
+    let mut head = Point2::ORIGIN;
 
     for inst in instructions {
         for _ in 0..inst.steps {
@@ -94,54 +68,86 @@ pub fn part_1_solution(instructions: Vec<Instruction>) -> usize {
     }
     visited.len()
 }
-pub fn part_2_solution(instructions: Vec<Instruction>) -> usize {
-    let mut visited: HashSet<Coord> = HashSet::new();
-    let mut tails = vec![Coord(0, 0); 9];
-    let mut head = Coord(0, 0);
+pub fn part_2_solution(instructions: &[Instruction]) -> usize {
+    let mut visited: HashSet<Point2<isize>> = HashSet::new();
+    let mut knots = [Point2::ORIGIN; 10];
 
     for inst in instructions {
         for _ in 0..inst.steps {
-            head += inst.dir;
-            tails[0] = update_tail(&head, &tails[0]);
-            for i in 0..8 {
-                tails[i + 1] = update_tail(&tails[i], &tails[i + 1]);
-            }
-            visited.insert(tails[8]);
+            pull(&mut knots, inst.dir);
+            visited.insert(knots[9]);
         }
     }
     visited.len()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::read_to_one_per_line;
+pub fn parse(input: &str) -> Result<Vec<Instruction>, Error> {
+    crate::parse_one_per_line(input)
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<Instruction>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    #[test]
-    fn example_part_1() {
-        let dirs = read_to_one_per_line::<Instruction>(P.example_path("_1")).unwrap();
-        let expected = 13;
-        assert_eq!(expected, part_1_solution(dirs))
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn input_part_1() {
-        let dirs = read_to_one_per_line::<Instruction>(P.input_path()).unwrap();
-        let expected = 5779;
-        assert_eq!(expected, part_1_solution(dirs))
+    fn pt1(instructions: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(instructions)
     }
 
-    #[test]
-    fn example_part_2() {
-        let dirs = read_to_one_per_line::<Instruction>(P.example_path("_1")).unwrap();
-        let expected = 1;
-        assert_eq!(expected, part_2_solution(dirs))
+    fn pt2(instructions: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(instructions)
     }
+}
+
+impl Visualize for Day09 {
+    /// part 2, a frame per step of the head, with the tail's trail behind it
+    fn visualize(instructions: &Self::Parsed, emit: &mut dyn FnMut(Frame)) {
+        // the knots never leave the box the head moves in
+        let mut head = Point2::ORIGIN;
+        let (mut min, mut max) = (head, head);
+        for inst in instructions {
+            head += inst.dir * inst.steps as isize;
+            min = Point2::new(min.x.min(head.x), min.y.min(head.y));
+            max = Point2::new(max.x.max(head.x), max.y.max(head.y));
+        }
+        let (width, height) = ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+
+        let mut knots = [Point2::ORIGIN; 10];
+        let mut visited: HashSet<Point2<isize>> = HashSet::from([Point2::ORIGIN]);
+        let mut steps = 0;
+        let mut draw = |knots: &[Point2<isize>], visited: &HashSet<Point2<isize>>, steps| {
+            let frame = Frame::from_fn(format!("step {}", steps), width, height, |(x, y)| {
+                let pos = min + Point2::new(x as isize, y as isize);
+                // the knot nearest the head is drawn on top
+                match knots.iter().position(|&k| k == pos) {
+                    Some(0) => Cell::new('H', Color::Red),
+                    Some(9) => Cell::new('T', Color::Green),
+                    Some(i) => Cell::new(char::from_digit(i as u32, 10).unwrap(), Color::Yellow),
+                    None if visited.contains(&pos) => Cell::new('#', Color::Grey),
+                    None if pos == Point2::ORIGIN => Cell::new('s', Color::Grey),
+                    None => Cell::EMPTY,
+                }
+            });
+            emit(frame);
+        };
 
-    #[test]
-    fn input_part_2() {
-        let dirs = read_to_one_per_line::<Instruction>(P.input_path()).unwrap();
-        let expected = 2331;
-        assert_eq!(expected, part_2_solution(dirs))
+        draw(&knots, &visited, steps);
+        for inst in instructions {
+            for _ in 0..inst.steps {
+                pull(&mut knots, inst.dir);
+                visited.insert(knots[9]);
+                steps += 1;
+                draw(&knots, &visited, steps);
+            }
+        }
     }
 }
+
+crate::examples!(Day09, pt1: [_1], pt2: [_1]);
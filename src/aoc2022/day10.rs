@@ -4,8 +4,14 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    grid::Grid,
+    ocr,
+    visualize::{Cell, Color, Frame, Visualize},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 10,
     name: "Cathode-Ray Tube",
@@ -17,21 +23,20 @@ pub enum Command {
 }
 
 impl FromStr for Command {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> anyhow::Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut splits = s.split(' ');
-        match splits.next().unwrap() {
+        let mut next = || splits.next().ok_or(ParseError::UnexpectedEnd);
+        match next()? {
             "noop" => Ok(Command::Noop),
-            "addx" => Ok(Command::Addx(
-                splits.next().unwrap().parse::<isize>().unwrap(),
-            )),
-            e => Err(anyhow::format_err!("unexpected val: {}", e)),
+            "addx" => Ok(Command::Addx(next()?.parse()?)),
+            cmd => Err(ParseError::UnexpectedToken(cmd.to_owned())),
         }
     }
 }
 
-pub fn part_1_solution(commands: Vec<Command>) -> isize {
+pub fn part_1_solution(commands: &[Command]) -> isize {
     let mut curr_score = 1;
     let mut scores = vec![curr_score];
 
@@ -47,23 +52,6 @@ pub fn part_1_solution(commands: Vec<Command>) -> isize {
             }
         }
     }
-    let ret = scores
-        .iter()
-        .enumerate()
-        .map(|(i, s)| {
-            let mut ret = if (s - ((i as isize - 1) % 40) as isize).abs() <= 1 {
-                "#"
-            } else {
-                " "
-            }
-            .to_owned();
-            if (i as isize) % 40 == 0 {
-                ret.push('\n')
-            }
-            ret
-        })
-        .collect::<String>();
-    println!("{}", ret);
     scores
         .iter()
         .enumerate()
@@ -77,81 +65,137 @@ pub fn part_1_solution(commands: Vec<Command>) -> isize {
         .sum()
 }
 
-pub fn update(
-    screen: &mut Vec<Vec<char>>,
-    scores: &mut Vec<isize>,
-    cycle: usize,
-    curr_score: isize,
-) {
-    if cycle == 20 || ((cycle as isize - 20) % 40 == 0) {
-        scores.push(curr_score);
+/// draws the pixel under the beam during `cycle`. cycles after the last pixel draw nothing
+pub fn update(screen: &mut Grid<bool>, cycle: usize, curr_score: isize) {
+    if cycle > screen.width() * screen.height() {
+        return;
     }
     let row = (cycle - 1) / 40;
     let col = (cycle - 1) % 40;
-    screen[row][col] = if (curr_score - ((cycle - 1) % 40) as isize).abs() <= 1 {
-        '#'
-    } else {
-        ' '
-    };
+    screen[(col, row)] = (curr_score - ((cycle - 1) % 40) as isize).abs() <= 1;
 }
-pub fn part_2_solution(commands: Vec<Command>) {
+/// the letters on the crt screen, or the screen itself if they can't be read
+pub fn part_2_solution(commands: &[Command]) -> String {
     let mut curr_score = 1;
-    let mut scores = vec![curr_score];
-
     let mut cycle = 0;
-    let mut screen = vec![vec!['?'; 40]; 6];
+    let mut screen = Grid::new(40, 6, false);
 
     for command in commands.iter() {
         match command {
             Command::Noop => {
                 cycle += 1;
-                update(&mut screen, &mut scores, cycle, curr_score);
+                update(&mut screen, cycle, curr_score);
             }
             Command::Addx(val) => {
                 cycle += 1;
-                update(&mut screen, &mut scores, cycle, curr_score);
+                update(&mut screen, cycle, curr_score);
                 cycle += 1;
-                update(&mut screen, &mut scores, cycle, curr_score);
+                update(&mut screen, cycle, curr_score);
                 curr_score += val;
             }
         }
     }
-    for row in screen.iter() {
-        let s = row.iter().join("");
-        println!("{:?}", s);
-    }
+    ocr::read(&screen).unwrap_or_else(|_| ocr::draw(&screen))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+pub fn parse(input: &str) -> Result<Vec<Command>, Error> {
+    crate::parse_one_per_line(input)
+}
 
-    use crate::read_to_one_per_line;
+pub struct Day10;
 
-    #[test]
-    fn example_part_1() {
-        let commands = read_to_one_per_line::<Command>(P.example_path("_1")).unwrap();
-        let expected = 13140;
-        assert_eq!(expected, part_1_solution(commands))
+impl Solution for Day10 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<Command>;
+    type OutputPt1 = isize;
+    type OutputPt2 = String;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn input_part_1() {
-        let commands = read_to_one_per_line::<Command>(P.input_path()).unwrap();
-        let expected = 12460;
-        assert_eq!(expected, part_1_solution(commands))
+    fn pt1(commands: &Self::Parsed) -> Self::OutputPt1 {
+        part_1_solution(commands)
     }
 
-    #[test]
-    fn example_part_2() {
-        let commands = read_to_one_per_line::<Command>(P.example_path("_1")).unwrap();
+    fn pt2(commands: &Self::Parsed) -> Self::OutputPt2 {
+        part_2_solution(commands)
+    }
+}
+
+impl Visualize for Day10 {
+    /// part 2, a frame per cycle as the beam draws the screen, with the sprite beneath it
+    fn visualize(commands: &Self::Parsed, emit: &mut dyn FnMut(Frame)) {
+        // the value of x during each cycle
+        let mut xs = Vec::new();
+        let mut x = 1;
+        for command in commands {
+            match command {
+                Command::Noop => xs.push(x),
+                Command::Addx(val) => {
+                    xs.extend([x, x]);
+                    x += val;
+                }
+            }
+        }
+
+        let mut screen = Grid::new(40, 6, false);
+        for (i, &x) in xs.iter().take(240).enumerate() {
+            let beam = (i % 40, i / 40);
+            screen[beam] = (x - beam.0 as isize).abs() <= 1;
+            let caption = format!("cycle {}, x = {}", i + 1, x);
+            emit(Frame::from_fn(caption, 40, 7, |pos| {
+                if pos.1 == 6 {
+                    // the sprite, on a row of its own
+                    match (x - pos.0 as isize).abs() <= 1 {
+                        true => Cell::new('#', Color::Cyan),
+                        false => Cell::plain(' '),
+                    }
+                } else if pos == beam {
+                    Cell::new(if screen[pos] { '#' } else { '.' }, Color::Red)
+                } else if pos.1 * 40 + pos.0 > i {
+                    Cell::plain(' ')
+                } else if screen[pos] {
+                    Cell::new('#', Color::Green)
+                } else {
+                    Cell::EMPTY
+                }
+            }));
+        }
+    }
+}
+
+crate::examples!(Day10, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
-        part_2_solution(commands);
+    #[test]
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(matches!(
+            parse(&input),
+            Err(Error::ParseError(ParseError::Located { location, .. })) if location.line == 3
+        ));
+        assert!(matches!(
+            "addx x".parse::<Command>(),
+            Err(ParseError::ParseIntError(_))
+        ));
+        assert!(matches!(
+            "jmp 2".parse::<Command>(),
+            Err(ParseError::UnexpectedToken(cmd)) if cmd == "jmp"
+        ));
+        assert!(matches!(
+            "addx".parse::<Command>(),
+            Err(ParseError::UnexpectedEnd)
+        ));
     }
 
     #[test]
-    fn input_part_2() {
-        let commands = read_to_one_per_line::<Command>(P.input_path()).unwrap();
-        part_2_solution(commands);
+    fn long_program() {
+        // 260 cycles, 20 past the last pixel
+        let commands = parse(&"addx 1\n".repeat(130)).unwrap();
+        part_2_solution(&commands);
     }
 }
@@ -2,13 +2,15 @@
 /// input: https://adventofcode.com/2022/day/11/input
 use std::{collections::VecDeque, str::FromStr};
 
-use itertools::Itertools;
-
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    math::lcm,
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 11,
-    name: "TODO",
+    name: "Monkey in the Middle",
 };
 
 #[derive(Debug, Clone)]
@@ -29,47 +31,32 @@ pub struct Monkey {
 }
 
 impl FromStr for Monkey {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> anyhow::Result<Self> {
-        let mut lines = s.lines();
-        let id = lines
-            .next()
-            .unwrap()
-            .chars()
-            .nth(7)
-            .unwrap()
-            .to_digit(10)
-            .unwrap() as usize;
-        let items_str = lines.next().unwrap().replace("  Starting items: ", "");
-        let items = items_str
-            .split(", ")
-            .map(|i| isize::from_str(i).unwrap())
-            .collect();
-        let op_str = lines
-            .next()
-            .unwrap()
-            .replace(' ', "")
-            .replace("Operation:new=old", "");
-        let operation = if op_str == String::from("*old") {
-            Op::Square
-        } else {
-            let cs = op_str.clone().chars().collect_vec();
-            let o = cs[0];
-            let num = cs[1..].to_owned().iter().collect::<String>();
-            match o {
-                '*' => Op::Mult(isize::from_str(&num)?),
-                '+' => Op::Add(isize::from_str(&num)?),
-                '-' => Op::Add(-isize::from_str(&num)?),
-                _ => {
-                    unreachable!()
-                }
-            }
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = &mut s.lines().map(str::trim);
+        let id = field(lines, "Monkey ")?.trim_end_matches(':').parse()?;
+        let items = field(lines, "Starting items:")?
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(isize::from_str)
+            .collect::<Result<_, _>>()?;
+        let op = field(lines, "Operation: new = old ")?;
+        let operation = match op.split_once(' ') {
+            Some(("*", "old")) => Op::Square,
+            Some(("*", num)) => Op::Mult(num.parse()?),
+            Some(("+", num)) => Op::Add(num.parse()?),
+            Some(("-", num)) => Op::Add(-num.parse::<isize>()?),
+            _ => return Err(ParseError::UnexpectedToken(op.to_owned())),
         };
-
-        let divisor = isize::from_str(lines.next().unwrap().split(' ').last().unwrap())?;
-        let true_monkey = usize::from_str(lines.next().unwrap().split(' ').last().unwrap())?;
-        let false_monkey = usize::from_str(lines.next().unwrap().split(' ').last().unwrap())?;
+        let divisor = field(lines, "Test: divisible by ")?;
+        let divisor = match divisor.parse()? {
+            d if d > 0 => d,
+            _ => return Err(ParseError::UnexpectedToken(divisor.to_owned())),
+        };
+        let true_monkey = field(lines, "If true: throw to monkey ")?.parse()?;
+        let false_monkey = field(lines, "If false: throw to monkey ")?.parse()?;
 
         Ok(Monkey {
             id,
@@ -82,11 +69,20 @@ impl FromStr for Monkey {
     }
 }
 
-fn pt1(input: &str) -> usize {
-    let mut monkeys = parse(input);
-    println!("{:?}", monkeys);
+/// the rest of the next line, which has to start with `prefix`
+fn field<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    prefix: &str,
+) -> Result<&'a str, ParseError> {
+    let line = lines.next().ok_or(ParseError::UnexpectedEnd)?;
+    line.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::UnexpectedToken(line.to_owned()))
+}
+
+fn pt1(monkeys: &[Monkey]) -> usize {
+    let mut monkeys = monkeys.to_vec();
     let mut inspects = vec![0; monkeys.len()];
-    for round in 0..20 {
+    for _ in 0..20 {
         for i in 0..monkeys.len() {
             let mut monk = monkeys[i].clone();
             for mut item in monk.items {
@@ -96,7 +92,7 @@ fn pt1(input: &str) -> usize {
                     Op::Square => item.pow(2),
                     Op::Add(v) => item + v,
                 };
-                item = item / 3;
+                item /= 3;
                 if item % monk.divisor == 0 {
                     monkeys[monk.true_monkey].items.push(item);
                 } else {
@@ -108,30 +104,14 @@ fn pt1(input: &str) -> usize {
     }
     inspects.sort();
     inspects.reverse();
-    println!("{:?}", inspects);
     inspects[0] * inspects[1]
 }
 
-fn gcd(a: isize, b: isize) -> isize {
-    if b == 0 {
-        a
-    } else {
-        gcd(b, a % b)
-    }
-}
-fn lcm(a: isize, b: isize) -> isize {
-    a * b / gcd(a, b)
-}
-
-fn pt2(input: &str) -> usize {
-    let mut monkeys = parse(input);
-    let bigdivisor = monkeys
-        .iter()
-        .map(|monk| monk.divisor)
-        .reduce(|a, b| lcm(a, b))
-        .unwrap();
+/// worry levels are kept modulo `bigdivisor`, a multiple of every monkey's divisor
+fn pt2(monkeys: &[Monkey], bigdivisor: isize) -> usize {
+    let mut monkeys = monkeys.to_vec();
     let mut inspects = vec![0; monkeys.len()];
-    for round in 0..10000 {
+    for _ in 0..10000 {
         for i in 0..monkeys.len() {
             let mut monk = monkeys[i].clone();
             for mut item in monk.items {
@@ -153,45 +133,108 @@ fn pt2(input: &str) -> usize {
     }
     inspects.sort();
     inspects.reverse();
-    println!("{:?}", inspects);
     inspects[0] * inspects[1]
 }
 
-fn parse(input: &str) -> Vec<Monkey> {
-    let ls = input.split("\r\n\r\n").into_iter();
-    ls.map(|ls| ls.parse().unwrap()).collect()
+/// the monkeys, which only throw to each other, and the lcm of their divisors
+fn parse(input: &str) -> Result<(Vec<Monkey>, isize), Error> {
+    let monkeys: Vec<Monkey> = crate::parse_chunks(input)?;
+    if monkeys.len() < 2 {
+        return Err(ParseError::InvalidSequence("fewer than two monkeys").into());
+    }
+    if monkeys
+        .iter()
+        .any(|m| m.true_monkey.max(m.false_monkey) >= monkeys.len())
+    {
+        return Err(ParseError::InvalidSequence("a throw to a monkey that isn't there").into());
+    }
+    let bigdivisor = monkeys
+        .iter()
+        .map(|monk| monk.divisor)
+        .try_fold(1, lcm)
+        .ok_or(ParseError::InvalidSequence(
+            "the divisors' lcm is too large",
+        ))?;
+    Ok((monkeys, bigdivisor))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const PROBLEM: Problem = P;
+    type Parsed = (Vec<Monkey>, isize);
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
+    }
+
+    fn pt1((monkeys, _): &Self::Parsed) -> Self::OutputPt1 {
+        pt1(monkeys)
+    }
+
+    fn pt2((monkeys, bigdivisor): &Self::Parsed) -> Self::OutputPt2 {
+        pt2(monkeys, *bigdivisor)
+    }
 }
 
+crate::examples!(Day11, pt1: [_1], pt2: [_1]);
+
 #[cfg(test)]
-mod tests {
+mod test {
     use super::*;
-    use std::fs::read_to_string;
-
-    #[test]
-    fn pt1_example() {
-        let input = read_to_string(P.example_path("_1")).unwrap();
-        let expected = 10605;
-        assert_eq!(pt1(&input), expected);
-    }
 
     #[test]
-    fn pt1_input() {
-        let input = read_to_string(P.input_path()).unwrap();
-        let expected = 57838;
-        assert_eq!(pt1(&input), expected);
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(matches!(
+            parse(&input),
+            Err(Error::ParseError(ParseError::Chunk { index: 0, .. }))
+        ));
+        let normalized = crate::normalize(&input);
+        let mut monkeys = normalized.split("\n\n").map(Monkey::from_str);
+        assert!(matches!(
+            monkeys.next(),
+            Some(Err(ParseError::UnexpectedToken(op))) if op == "/ 19"
+        ));
+        assert!(matches!(
+            monkeys.next(),
+            Some(Err(ParseError::ParseIntError(_)))
+        ));
+        assert!(matches!(
+            monkeys.next(),
+            Some(Err(ParseError::UnexpectedEnd))
+        ));
     }
 
     #[test]
-    fn pt2_example() {
-        let input = read_to_string(P.example_path("_1")).unwrap();
-        let expected = 2713310158;
-        assert_eq!(pt2(&input), expected);
+    fn divisors() {
+        let input = std::fs::read_to_string(P.example_path("_1")).unwrap();
+        let (_, bigdivisor) = parse(&input).unwrap();
+        assert_eq!(bigdivisor, 23 * 19 * 13 * 17);
+        let negative = input.replace("divisible by 23", "divisible by -3");
+        assert!(matches!(
+            parse(&negative),
+            Err(Error::ParseError(ParseError::Chunk { index: 0, .. }))
+        ));
+        let huge = input
+            .replace("divisible by 23", "divisible by 3999999979")
+            .replace("divisible by 19", "divisible by 3999999967")
+            .replace("divisible by 13", "divisible by 3999999959");
+        assert!(matches!(
+            parse(&huge),
+            Err(Error::ParseError(ParseError::InvalidSequence(_)))
+        ));
     }
 
     #[test]
-    fn pt2_input() {
-        let input = read_to_string(P.input_path()).unwrap();
-        let expected = 15050382231;
-        assert_eq!(pt2(&input), expected);
+    fn missing_monkey() {
+        let input = std::fs::read_to_string(P.example_path("_1")).unwrap();
+        let input = input.replace("throw to monkey 3", "throw to monkey 4");
+        assert!(matches!(
+            parse(&input),
+            Err(Error::ParseError(ParseError::InvalidSequence(_)))
+        ));
     }
 }
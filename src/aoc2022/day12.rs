@@ -1,165 +1,150 @@
 /// problem: https://adventofcode.com/2022/day/12
 /// input: https://adventofcode.com/2022/day/12/input
-use crate::Problem;
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    grid::{Grid, Pos},
+    image::{gradient, Export, Rgb, RED, WHITE},
+    search::bfs,
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 12,
-    name: "TODO",
+    name: "Hill Climbing Algorithm",
 };
 
-fn get_neighbours(
-    graph: &Vec<Vec<u8>>,
-    coordinate: &(usize, usize),
-    xmax: usize,
-    ymax: usize,
-) -> Vec<(usize, usize)> {
-    let (x, y) = *coordinate;
-    let curr = graph[x][y];
-    let mut neighbours = Vec::new();
-    if x > 0 {
-        if graph[x - 1][y] <= curr + 1 {
-            neighbours.push((x - 1, y));
-        }
-    }
-    if x < xmax {
-        if graph[x + 1][y] <= curr + 1 {
-            neighbours.push((x + 1, y));
-        }
-    }
-    if y > 0 {
-        if graph[x][y - 1] <= curr + 1 {
-            neighbours.push((x, y - 1));
-        }
-    }
-    if y < ymax {
-        if graph[x][y + 1] <= curr + 1 {
-            neighbours.push((x, y + 1));
-        }
-    }
-    neighbours
+/// the neighbours that are at most one higher than `coordinate`
+fn get_neighbours(graph: &Grid<u8>, coordinate: Pos) -> impl Iterator<Item = Pos> + '_ {
+    let curr = graph[coordinate];
+    graph
+        .neighbours4(coordinate)
+        .filter(move |&n| graph[n] <= curr + 1)
+}
+
+/// the fewest steps from any of `starts` to `end`, and the way there.
+/// `None` if the end can't be reached
+fn route(
+    graph: &Grid<u8>,
+    starts: impl IntoIterator<Item = Pos>,
+    end: &Pos,
+) -> Option<(usize, Vec<Pos>)> {
+    bfs(
+        starts,
+        |&node| get_neighbours(graph, node),
+        |node| node == end,
+    )
+}
+
+fn pt1(height_map: &HeightMap) -> usize {
+    height_map.route.0
+}
+
+fn pt2(HeightMap { graph, end, .. }: &HeightMap) -> usize {
+    route(graph, graph.find_all(&b'a'), end)
+        .expect("the start is an 'a', and parsing found its route to the end")
+        .0
 }
 
-/// return the shortest path as per djikstra
-fn djikstra(graph: &Vec<Vec<u8>>, start: &(usize, usize), end: &(usize, usize)) -> usize {
-    // start is usually 0,0
+/// heights 'a' to 'z', with the start at height 'a' and the end at height 'z'
+pub struct HeightMap {
+    graph: Grid<u8>,
+    end: Pos,
+    /// part 1's route from the start to the end, and how many steps it takes
+    route: (usize, Vec<Pos>),
+}
 
-    let xmax = graph.len() - 1;
-    let ymax = graph[0].len() - 1;
+/// the end must be reachable from the start
+fn parse(input: &str) -> Result<HeightMap, Error> {
+    let mut graph = Grid::parse_with(input, |c| {
+        matches!(c, 'a'..='z' | 'S' | 'E').then_some(c as u8)
+    })?;
+    let start = graph
+        .find(&b'S')
+        .ok_or(ParseError::InvalidSequence("no start 'S'"))?;
+    let end = graph
+        .find(&b'E')
+        .ok_or(ParseError::InvalidSequence("no end 'E'"))?;
+    graph[start] = b'a';
+    graph[end] = b'z';
+    let route = route(&graph, [start], &end)
+        .ok_or(ParseError::InvalidSequence("no route from 'S' to 'E'"))?;
+    Ok(HeightMap { graph, end, route })
+}
 
-    // initialize the distances
-    let mut distances = vec![vec![usize::MAX; ymax + 1]; xmax + 1];
-    distances[start.0][start.1] = 0;
+pub struct Day12;
 
-    // initialize the visited
-    let mut visited = vec![vec![false; ymax + 1]; xmax + 1];
-    visited[start.0][start.1] = true;
+impl Solution for Day12 {
+    const PROBLEM: Problem = P;
+    type Parsed = HeightMap;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
 
-    let mut frontier = get_neighbours(&graph, &start, xmax, ymax); // TODO: use a queue/heap
-    for node in frontier.iter() {
-        distances[node.0][node.1] = 1;
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
-    let mut next_nearest_node = *start;
-    while next_nearest_node != *end {
-        // get next best candidate
-        let mut min_dist = usize::MAX;
-        next_nearest_node = *start;
-        for node in frontier.iter() {
-            if distances[node.0][node.1] < min_dist {
-                min_dist = distances[node.0][node.1];
-                next_nearest_node = *node;
-            }
-        }
-        if next_nearest_node == *start {
-            return usize::MAX;
-        }
 
-        // visit node
-        visited[next_nearest_node.0][next_nearest_node.1] = true;
-        // add to frontier and update distances
-        for node in get_neighbours(&graph, &next_nearest_node, xmax, ymax) {
-            if distances[node.0][node.1] > distances[next_nearest_node.0][next_nearest_node.1] + 1 {
-                distances[node.0][node.1] = distances[next_nearest_node.0][next_nearest_node.1] + 1;
-            }
-            if !visited[node.0][node.1] {
-                frontier.push(node);
-            }
-        }
-        frontier.retain(|node| *node != next_nearest_node);
+    fn pt1(height_map: &Self::Parsed) -> Self::OutputPt1 {
+        pt1(height_map)
     }
-    for dist in &distances {
-        println!("{:?}", dist);
+
+    fn pt2(height_map: &Self::Parsed) -> Self::OutputPt2 {
+        pt2(height_map)
     }
-    distances[next_nearest_node.0][next_nearest_node.1]
 }
 
-fn find_val(graph: &Vec<Vec<u8>>, val: u8) -> Vec<(usize, usize)> {
-    let mut coords = Vec::new();
-    for row in 0..graph.len() {
-        for col in 0..graph[0].len() {
-            if graph[row][col] == val {
-                coords.push((row, col));
-            }
+impl Export for Day12 {
+    /// the heightmap, low ground dark and high ground light, with part 1's route
+    /// drawn a step per picture
+    fn export(height_map: &Self::Parsed, emit: &mut dyn FnMut(Grid<Rgb>)) {
+        // heights outside 'a'..='z' are clamped to the ends of the gradient
+        let t = |h: u8| h.saturating_sub(b'a') as f64 / 25.0;
+        let mut image = height_map
+            .graph
+            .map(|&h| gradient([20, 60, 30], [235, 235, 220], t(h)));
+        image[height_map.end] = WHITE;
+        for &pos in height_map.route.1.iter() {
+            image[pos] = RED;
+            emit(image.clone());
         }
     }
-    coords
-}
-
-fn pt1(input: &str) -> usize {
-    let graph = parse(input);
-    // let start = find_val(&graph, "a".as_bytes()[0] - 1)[0];
-    // let end = find_val(&graph, "z".as_bytes()[0] + 1)[0];
-    // println!("{:?}{:?}", start, end);
-    djikstra(&graph, &(20, 0), &(20, 43))
-}
-
-fn pt2(input: &str) -> usize {
-    let graph = parse(input);
-    let starts = find_val(&graph, "a".as_bytes()[0]);
-    let exit = find_val(&graph, "z".as_bytes()[0] + 1)[0];
-    starts
-        .iter()
-        .map(|start| djikstra(&graph, start, &exit))
-        .min()
-        .unwrap()
 }
 
-fn parse(input: &str) -> Vec<Vec<u8>> {
-    input
-        .replace('S', "a") // std::str::from_utf8(&["a".as_bytes()[0] - 1]).unwrap())
-        // .replace('S', std::str::from_utf8(&["a".as_bytes()[0] - 1]).unwrap())
-        .replace('E', "z") // std::str::from_utf8(&["z".as_bytes()[0] + 1]).unwrap()) // so hacky
-        // .replace('E', std::str::from_utf8(&["z".as_bytes()[0] + 1]).unwrap()) // so hacky
-        .lines()
-        .map(|l| l.bytes().collect())
-        .collect()
-}
+crate::examples!(Day12, pt1: [_1], pt2: [_1]);
 
 #[cfg(test)]
-mod tests {
+mod test {
     use super::*;
-    use std::fs::read_to_string;
-    // in process of refactoring
-    // #[test]
-    // fn pt1_example() {
-    //     let input = read_to_string(P.example_path("_1")).expect("no such file");
-    //     assert_eq!(pt1(&input), 31);
-    // }
-
-    // #[test]
-    // fn pt1_input() {
-    //     let input = read_to_string(P.input_path()).expect("no such file");
-    //     assert_eq!(pt1(&input), 339);
-    // }
-
-    // #[test]
-    // fn pt2_example() {
-    //     let input = read_to_string(P.example_path("_1")).expect("no such file");
-    //     assert_eq!(pt2(&input), 29);
-    // }
-
-    // #[test]
-    // fn pt2_input() {
-    //     let input = read_to_string(P.input_path()).expect("no such file");
-    //     assert_eq!(pt2(&input), 332); // now 334?
-    // }
+
+    #[test]
+    fn unreachable() {
+        // the end is two higher than everything around it
+        let input = "SbcE\nabcd";
+        assert!(matches!(
+            parse(input),
+            Err(Error::ParseError(ParseError::InvalidSequence(_)))
+        ));
+    }
+
+    #[test]
+    fn odd_heights() {
+        assert!(matches!(
+            parse("Sbc\nd{E"),
+            Err(Error::ParseError(ParseError::Located { location, .. }))
+                if (location.line, location.col) == (2, 2)
+        ));
+        assert!(parse("Sb1\n~zE").is_err());
+    }
+
+    #[test]
+    fn export_odd_heights() {
+        let graph: Grid<u8> = "ab.\n{zA".parse().unwrap();
+        let (start, end) = (graph.find(&b'a').unwrap(), graph.find(&b'b').unwrap());
+        let route = route(&graph, [start], &end).unwrap();
+        let height_map = HeightMap { graph, end, route };
+        let mut frames = Vec::new();
+        Day12::export(&height_map, &mut |image| frames.push(image));
+        let last = frames.last().unwrap();
+        assert_eq!(last[end], RED);
+        assert_eq!(last[(2, 0)], last[(2, 1)]);
+    }
 }
@@ -1,11 +1,16 @@
-use itertools::Itertools;
-
-use crate::Problem;
-use std::str::{Chars, FromStr};
 /// problem: https://adventofcode.com/2022/day/13
 /// input: https://adventofcode.com/2022/day/13/input
+use itertools::Itertools;
 
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    Problem, Solution,
+};
+use std::{
+    iter::Peekable,
+    str::{Chars, FromStr},
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 13,
     name: "Distress Signal",
@@ -18,39 +23,49 @@ pub enum Node {
 }
 
 impl FromStr for Node {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> anyhow::Result<Self> {
-        let cs = &mut s.chars();
-        cs.next(); // burn first [
-        Node::from_chars(cs)
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars = &mut s.trim().chars().peekable();
+        let node = Node::from_chars(chars)?;
+        match chars.next() {
+            None => Ok(node),
+            Some(c) => Err(ParseError::UnexpectedChar(c)),
+        }
     }
 }
 impl Node {
-    fn from_chars(chars: &mut Chars) -> anyhow::Result<Self> {
-        // first char
-        let mut children = Vec::new();
-        while let Some(c) = chars.next() {
-            match c {
-                ']' => break, // end of chars
-                '[' => children.push(Node::from_chars(chars)?),
-                token if token.is_ascii_digit() => {
-                    let mut v = String::from(token);
-                    for ch in chars.take_while(|&tok| tok.is_digit(10)) {
-                        v.push(ch);
+    /// parses a list or a value off the front of `chars`
+    fn from_chars(chars: &mut Peekable<Chars>) -> Result<Self, ParseError> {
+        match chars.next().ok_or(ParseError::UnexpectedEnd)? {
+            '[' => {
+                let mut children = Vec::new();
+                if chars.next_if_eq(&']').is_some() {
+                    return Ok(Node::List(children));
+                }
+                loop {
+                    children.push(Node::from_chars(chars)?);
+                    match chars.next().ok_or(ParseError::UnexpectedEnd)? {
+                        ',' => {}
+                        ']' => return Ok(Node::List(children)),
+                        c => return Err(ParseError::UnexpectedChar(c)),
                     }
-                    children.push(Node::Val(usize::from_str(&v).unwrap()));
                 }
-                ',' => {}
-                _ => unreachable!(),
             }
+            token if token.is_ascii_digit() => {
+                let mut v = String::from(token);
+                while let Some(ch) = chars.next_if(char::is_ascii_digit) {
+                    v.push(ch);
+                }
+                Ok(Node::Val(v.parse()?))
+            }
+            c => Err(ParseError::UnexpectedChar(c)),
         }
-        Ok(Node::List(children))
     }
 }
 
 #[derive(Debug)]
-struct Pair {
+pub struct Pair {
     left: Node,
     right: Node,
 }
@@ -86,22 +101,19 @@ impl Pair {
 }
 
 impl FromStr for Pair {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> anyhow::Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut l = s.lines();
+        let mut next = || l.next().ok_or(ParseError::UnexpectedEnd)?.parse::<Node>();
         Ok(Self {
-            left: l.next().unwrap().to_owned().parse::<Node>().unwrap(),
-            right: l.next().unwrap().to_owned().parse::<Node>().unwrap(),
+            left: next()?,
+            right: next()?,
         })
     }
 }
 
-fn pt1(input: &str) -> usize {
-    let pairs = parse(input);
-    for (i, pair) in pairs.iter().enumerate() {
-        println!("{} {}, ", i, pair.is_in_order());
-    }
+fn pt1(pairs: &[Pair]) -> usize {
     pairs
         .iter()
         .enumerate()
@@ -109,60 +121,67 @@ fn pt1(input: &str) -> usize {
         .sum()
 }
 
-fn pt2(input: &str) -> usize {
-    let mut pairs = parse_pt2(input);
+fn pt2(pairs: &[Pair]) -> usize {
+    let mut pairs: Vec<Node> = pairs
+        .iter()
+        .flat_map(|pair| [pair.left.clone(), pair.right.clone()])
+        .collect();
     let dividers: Vec<Node> = vec!["[[2]]".parse().unwrap(), "[[6]]".parse().unwrap()];
     pairs.extend(dividers.clone());
     pairs
         .iter()
         .sorted()
-        .positions(|x| dividers.contains(&x))
+        .positions(|x| dividers.contains(x))
         .fold(1, |acc, x| acc * (x + 1))
 }
 
-fn parse(input: &str) -> Vec<Pair> {
-    input
-        .replace('\r', "")
-        .split("\n\n")
-        .map(|l| l.parse().unwrap())
-        .collect()
+fn parse(input: &str) -> Result<Vec<Pair>, Error> {
+    crate::parse_chunks(input)
 }
 
-fn parse_pt2(input: &str) -> Vec<Node> {
-    input
-        .replace('\r', "")
-        .replace("\n\n", "\n")
-        .lines()
-        .map(|l| l.parse().unwrap())
-        .collect()
+pub struct Day13;
+
+impl Solution for Day13 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<Pair>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
+    }
+
+    fn pt1(pairs: &Self::Parsed) -> Self::OutputPt1 {
+        pt1(pairs)
+    }
+
+    fn pt2(pairs: &Self::Parsed) -> Self::OutputPt2 {
+        pt2(pairs)
+    }
 }
 
+
+crate::examples!(Day13, pt1: [_1], pt2: [_1]);
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs::read_to_string;
 
-    #[test]
-    fn pt1_example() {
-        let input = read_to_string(P.example_path("_1")).expect("no such file");
-        assert_eq!(pt1(&input), 13);
-    }
     
-    #[test]
-    fn pt1_input() {
-        let input = read_to_string(P.input_path()).expect("no such file");
-        assert_eq!(pt1(&input), 6544); // too low 7592 too high
-    }
-
-    #[test]
-    fn pt2_example() {
-        let input = read_to_string(P.example_path("_1")).expect("no such file");
-        assert_eq!(pt2(&input), 140);
-    }
 
     #[test]
-    fn pt2_input() {
-        let input = read_to_string(P.input_path()).expect("no such file");
-        assert_eq!(pt2(&input), 19493);
+    fn malformed() {
+        let input = read_to_string(P.fixture_path("_malformed")).expect("no such file");
+        assert!(matches!(
+            parse(&input),
+            Err(Error::ParseError(ParseError::Chunk { index: 0, .. }))
+        ));
+        let mut lines = input.lines().map(Node::from_str);
+        assert!(matches!(lines.next(), Some(Err(ParseError::UnexpectedEnd))));
+        assert!(matches!(
+            lines.nth(2),
+            Some(Err(ParseError::UnexpectedChar('a')))
+        ));
     }
 }
@@ -1,62 +1,85 @@
-
-use crate::Problem;
 /// problem: https://adventofcode.com/2022/day/14
 /// input: https://adventofcode.com/2022/day/14/input
-
-const P: Problem = Problem {
-    year: 2022,
-    day: 14,
-    name: "TODO",
-};
 use std::{
     cmp::{max, min},
     str::FromStr,
 };
 
+use crate::{
+    error::{Error, ParseError},
+    grid::Grid,
+    image::{Export, Rgb, RED},
+    simulate,
+    visualize::{Cell, Color, Frame, Visualize},
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
+    year: 2022,
+    day: 14,
+    name: "Regolith Reservoir",
+};
+
+/// the width of the cave
+const WIDTH: usize = 1000;
+/// the deepest a rock can be. part 2's floor is two below it and its sand spreads a column
+/// either side of 500 per row, so any deeper and the pile would spill off the cave
+const MAX_DEPTH: usize = WIDTH / 2 - 3;
+
 #[derive(Debug)]
 pub struct RockPath(Vec<[usize; 2]>);
 
 impl FromStr for RockPath {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> anyhow::Result<Self> {
-        Ok(Self(
-            s.split(" -> ")
-                .map(|p| {
-                    let (s, f) = p.split_once(',').unwrap();
-                    [s.parse::<usize>().unwrap(), f.parse::<usize>().unwrap()]
-                })
-                .collect(),
-        ))
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(" -> ")
+            .map(|p| {
+                let (x, y) = p
+                    .split_once(',')
+                    .ok_or_else(|| ParseError::UnexpectedToken(p.to_owned()))?;
+                let (x, y) = (x.parse()?, y.parse()?);
+                if x >= WIDTH || y > MAX_DEPTH {
+                    return Err(ParseError::UnexpectedToken(p.to_owned()));
+                }
+                Ok([x, y])
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
     }
 }
 
-fn drop_sand(rocks: &mut Vec<Vec<bool>>, max_depth: usize) -> bool {
+fn drop_sand(rocks: &mut Grid<bool>, max_depth: usize) -> bool {
     let mut s = (500, 0);
     while s.1 < max_depth {
-        if !rocks[s.0][s.1 + 1] {
+        if !rocks[(s.0, s.1 + 1)] {
             s.1 += 1;
             continue;
-        } else if !rocks[s.0 - 1][s.1 + 1] {
+        } else if !rocks[(s.0 - 1, s.1 + 1)] {
             s.1 += 1;
             s.0 -= 1;
             continue;
-        } else if !rocks[s.0 + 1][s.1 + 1] {
+        } else if !rocks[(s.0 + 1, s.1 + 1)] {
             s.1 += 1;
             s.0 += 1;
             continue;
-        } else if !rocks[s.0][s.1] && rocks[s.0][s.1 + 1] {
-            rocks[s.0][s.1] = true;
+        } else if !rocks[(s.0, s.1)] && rocks[(s.0, s.1 + 1)] {
+            rocks[(s.0, s.1)] = true;
             return true;
-        } else if rocks[s.0][s.1] {
+        } else if rocks[(s.0, s.1)] {
             return false;
         }
     }
-    return false;
+    false
+}
+
+/// drops sand until a grain doesn't come to rest, returning how many did
+fn settle(mut rocks: Grid<bool>, max_depth: usize) -> usize {
+    simulate::run_while(&mut rocks, |rocks| drop_sand(rocks, max_depth))
 }
-fn pt1(input: &str) -> usize {
-    let rockpaths = parse(input);
-    let mut rocks = vec![vec![false; 1000]; 1000];
+
+/// the rocks drawn by each path, and the depth of the deepest
+fn rocks(rockpaths: &[RockPath]) -> (Grid<bool>, usize) {
+    let mut rocks = Grid::new(WIDTH, MAX_DEPTH + 3, false);
     let mut max_depth = 0;
     for rockpath in rockpaths {
         for window in rockpath.0.windows(2) {
@@ -69,81 +92,163 @@ fn pt1(input: &str) -> usize {
             max_depth = max(max_depth, y2);
             for x in x1..=x2 {
                 for y in y1..=y2 {
-                    rocks[x][y] = true;
+                    rocks[(x, y)] = true;
                 }
             }
         }
     }
+    (rocks, max_depth)
+}
 
-    let mut i = 0;
-    while drop_sand(&mut rocks, max_depth) {
-        i += 1;
-    }
-    i
+/// what's at each position of the cave
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+    Source,
 }
 
-fn pt2(input: &str) -> usize {
-    let rockpaths = parse(input);
-    let mut rocks = vec![vec![false; 1000]; 1000];
-    let mut max_depth = 0;
-    for rockpath in rockpaths {
-        for window in rockpath.0.windows(2) {
-            let (x1, x2, y1, y2) = (
-                min(window[0][0], window[1][0]),
-                max(window[0][0], window[1][0]),
-                min(window[0][1], window[1][1]),
-                max(window[0][1], window[1][1]),
-            );
-            max_depth = max(max_depth, y2);
-            for x in x1..=x2 {
-                for y in y1..=y2 {
-                    rocks[x][y] = true;
-                }
+/// part 1 a grain at a time, calling `f` with the cave before the first grain and after each
+/// one comes to rest. the cave is cut down to the columns sand can settle in
+fn pour(rockpaths: &[RockPath], mut f: impl FnMut(&Grid<Tile>, usize)) {
+    let (rocks, max_depth) = rocks(rockpaths);
+    let xs = rockpaths.iter().flat_map(|path| path.0.iter().map(|p| p[0]));
+    let x0 = xs.clone().min().unwrap_or(500).saturating_sub(1);
+    let x1 = (xs.max().unwrap_or(500) + 1).min(WIDTH - 1);
+    let crop = |cave: &Grid<bool>| {
+        Grid::from_fn(x1 - x0 + 1, max_depth + 1, |(x, y)| {
+            let pos = (x0 + x, y);
+            match (rocks[pos], cave[pos]) {
+                (true, _) => Tile::Rock,
+                (false, true) => Tile::Sand,
+                _ if pos == (500, 0) => Tile::Source,
+                _ => Tile::Air,
             }
-        }
+        })
+    };
+
+    let mut cave = rocks.clone();
+    let mut grains = 0;
+    f(&crop(&cave), grains);
+    while drop_sand(&mut cave, max_depth) {
+        grains += 1;
+        f(&crop(&cave), grains);
     }
+}
+
+fn pt1(rockpaths: &[RockPath]) -> usize {
+    let (rocks, max_depth) = rocks(rockpaths);
+    settle(rocks, max_depth)
+}
+
+fn pt2(rockpaths: &[RockPath]) -> usize {
+    let (mut rocks, mut max_depth) = rocks(rockpaths);
     max_depth += 2;
-    for x in 0..1000 {
-        rocks[x][max_depth] = true;
+    for x in 0..WIDTH {
+        rocks[(x, max_depth)] = true;
     }
 
-    let mut i = 0;
-    while drop_sand(&mut rocks, max_depth +1) {
-        i += 1;
-    }
-    i
+    settle(rocks, max_depth + 1)
 }
 
-fn parse(input: &str) -> Vec<RockPath> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+fn parse(input: &str) -> Result<Vec<RockPath>, Error> {
+    crate::parse_one_per_line(input)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::fs::read_to_string;
+pub struct Day14;
 
-    #[test]
-    fn pt1_example() {
-        let input = read_to_string(P.example_path("_1")).expect("no such file");
-        assert_eq!(pt1(&input), 24);
+impl Solution for Day14 {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<RockPath>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    #[test]
-    fn pt1_input() {
-        let input = read_to_string(P.input_path()).expect("no such file");
-        assert_eq!(pt1(&input), 674);
+    fn pt1(rockpaths: &Self::Parsed) -> Self::OutputPt1 {
+        pt1(rockpaths)
     }
 
+    fn pt2(rockpaths: &Self::Parsed) -> Self::OutputPt2 {
+        pt2(rockpaths)
+    }
+}
+
+impl Visualize for Day14 {
+    /// part 1, a frame per grain of sand that comes to rest
+    fn visualize(rockpaths: &Self::Parsed, emit: &mut dyn FnMut(Frame)) {
+        pour(rockpaths, |cave, grains| {
+            emit(Frame::from_fn(
+                format!("{} grains", grains),
+                cave.width(),
+                cave.height(),
+                |pos| match cave[pos] {
+                    Tile::Air => Cell::plain(' '),
+                    Tile::Rock => Cell::new('#', Color::Grey),
+                    Tile::Sand => Cell::new('o', Color::Yellow),
+                    Tile::Source => Cell::new('+', Color::Red),
+                },
+            ))
+        });
+    }
+}
+
+impl Export for Day14 {
+    /// part 1, a picture per grain of sand that comes to rest
+    fn export(rockpaths: &Self::Parsed, emit: &mut dyn FnMut(Grid<Rgb>)) {
+        pour(rockpaths, |cave, _| {
+            emit(cave.map(|tile| match tile {
+                Tile::Air => [25, 25, 40],
+                Tile::Rock => [110, 110, 110],
+                Tile::Sand => [230, 190, 90],
+                Tile::Source => RED,
+            }))
+        });
+    }
+}
+
+crate::examples!(Day14, pt1: [_1], pt2: [_1]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
-    fn pt2_example() {
-        let input = read_to_string(P.example_path("_1")).expect("no such file");
-        assert_eq!(pt2(&input), 93);
+    fn malformed() {
+        let input = std::fs::read_to_string(P.fixture_path("_malformed")).unwrap();
+        assert!(matches!(
+            parse(&input),
+            Err(Error::ParseError(ParseError::Located { location, .. })) if location.line == 2
+        ));
+        assert!(matches!(
+            "503,4 -> 502".parse::<RockPath>(),
+            Err(ParseError::UnexpectedToken(p)) if p == "502"
+        ));
+        assert!(matches!(
+            "503,4 -> 502,x".parse::<RockPath>(),
+            Err(ParseError::ParseIntError(_))
+        ));
+        assert!(matches!(
+            "503,4 -> 1000,4".parse::<RockPath>(),
+            Err(ParseError::UnexpectedToken(p)) if p == "1000,4"
+        ));
+        assert!(matches!(
+            "503,4 -> 503,498".parse::<RockPath>(),
+            Err(ParseError::UnexpectedToken(p)) if p == "503,498"
+        ));
     }
 
     #[test]
-    fn pt2_input() {
-        let input = read_to_string(P.input_path()).expect("no such file");
-        assert_eq!(pt2(&input), 24958);
+    fn pour_from_the_edge() {
+        let rockpaths = parse("0,5 -> 0,9 -> 999,9").unwrap();
+        let mut frames = 0;
+        pour(&rockpaths, |cave, _| {
+            assert_eq!(cave.width(), WIDTH);
+            frames += 1;
+        });
+        assert_eq!(frames, pt1(&rockpaths) + 1);
     }
 }
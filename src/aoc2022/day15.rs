@@ -1,153 +1,127 @@
-use std::{ops::RangeInclusive, str::FromStr};
-
-use itertools::Itertools;
-
-use crate::Problem;
 /// problem: https://adventofcode.com/2022/day/15
 /// input: https://adventofcode.com/2022/day/15/input
+use std::{collections::HashSet, ops::RangeInclusive, str::FromStr};
 
-const P: Problem = Problem {
+use crate::{
+    error::{Error, ParseError},
+    geom::Point2,
+    range_set::RangeSet,
+    Problem, Solution,
+};
+pub const P: Problem = Problem {
     year: 2022,
     day: 15,
-    name: "TODO",
+    name: "Beacon Exclusion Zone",
 };
 
 #[derive(Debug)]
 pub struct Sensor {
-    origin: (isize, isize),
-    beacon: (isize, isize),
+    origin: Point2<isize>,
+    beacon: Point2<isize>,
     range: usize,
 }
 impl FromStr for Sensor {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> anyhow::Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         //Sensor at x=2, y=18: closest beacon is at x=-2, y=15
         let (origin, beacon) = s
             .trim()
-            .replace("Sensor at x=", "")
-            .replace(", y=", ",")
-            .replace(": closest beacon is at x=", "|")
-            .split('|')
-            .map(|coords| {
-                coords
-                    .split(',')
-                    .map(|n| n.parse().expect("Should be usize"))
-                    .collect_tuple::<(isize, isize)>()
-                    .expect("must be 2")
-            })
-            .collect_tuple::<(_, _)>()
-            .expect("must be 2");
+            .strip_prefix("Sensor at ")
+            .and_then(|s| s.split_once(": closest beacon is at "))
+            .ok_or(ParseError::InvalidSequence("expected a sensor and its closest beacon"))?;
+        let origin: Point2<isize> = origin.parse()?;
+        let beacon: Point2<isize> = beacon.parse()?;
 
         Ok(Self {
-            origin: origin,
-            beacon: beacon,
-            range: Self::manhattan(origin, beacon),
+            origin,
+            beacon,
+            range: origin.manhattan(beacon) as usize,
         })
     }
 }
 
 impl Sensor {
-    fn manhattan(p1: (isize, isize), p2: (isize, isize)) -> usize {
-        ((p1.0 - p2.0).abs() + (p1.1 - p2.1).abs()) as usize
-    }
-
-    fn is_in_range(&self, p2: (isize, isize)) -> bool {
-        Self::manhattan(self.origin, p2) <= self.range && p2 != self.beacon
-    }
-
-    fn out_of_range(&self, p2: (isize, isize)) -> bool {
-        Self::manhattan(self.origin, p2) > self.range
+    /// the xs on row `y` that are no further from the sensor than its beacon.
+    /// empty if the row is out of range
+    fn coverage(&self, y: isize) -> RangeInclusive<isize> {
+        let delta = self.range as isize - (self.origin.y - y).abs();
+        self.origin.x - delta..=self.origin.x + delta
     }
 }
 
-fn pt1(input: &str, target: isize) -> usize {
-    // TODO: speed up lol
-    let sensors = parse(input);
-    let mut count = 0;
-    for x in -10000000..10000000 {
-        if sensors.iter().any(|sensor| sensor.is_in_range((x, target))) {
-            count += 1;
-        }
-    }
-    count
+fn pt1(sensors: &[Sensor], target: isize) -> usize {
+    let covered: RangeSet<isize> = sensors.iter().map(|sensor| sensor.coverage(target)).collect();
+    // a known beacon is a place a beacon can be
+    let beacons: HashSet<isize> = sensors
+        .iter()
+        .filter(|sensor| sensor.beacon.y == target && covered.contains(sensor.beacon.x))
+        .map(|sensor| sensor.beacon.x)
+        .collect();
+    covered.len() as usize - beacons.len()
 }
 
-trait InclusiveRangeExt {
-    // TODO impl merge
-    fn contains_range(&self, other: &Self) -> bool;
+/// the first cell in the square from 0 to `area` on both axes that no sensor covers
+fn distress(sensors: &[Sensor], area: isize) -> Option<Point2<isize>> {
+    (0..=area).find_map(|y| {
+        let covered: RangeSet<isize> = sensors.iter().map(|sensor| sensor.coverage(y)).collect();
+        let gap = covered.complement(0..=area).iter().next()?.clone();
+        Some(Point2::new(*gap.start(), y))
+    })
+}
 
-    fn overlaps(&self, other: &Self) -> bool;
+/// the sensors, the row part 1 asks about, and the distress beacon part 2 looks for
+pub struct Scan {
+    sensors: Vec<Sensor>,
+    row: isize,
+    distress: Point2<isize>,
+}
 
-    fn is_adjacent(&self, other: &Self) -> bool;
+/// the example asks about row 10 and a 20x20 area, the input about row 2000000 and a
+/// 4000000x4000000 area. the example's sensors are all within 100 of the origin
+fn parse(input: &str) -> Result<Scan, Error> {
+    let sensors: Vec<Sensor> = crate::parse_one_per_line(input)?;
+    let example = sensors
+        .iter()
+        .all(|sensor| sensor.origin.x.abs().max(sensor.origin.y.abs()) <= 100);
+    let (row, area) = if example {
+        (10, 20)
+    } else {
+        (2000000, 4000000)
+    };
+    let distress = distress(&sensors, area).ok_or(ParseError::InvalidSequence(
+        "every cell of the search area is in range of a sensor",
+    ))?;
+    Ok(Scan {
+        sensors,
+        row,
+        distress,
+    })
 }
 
-impl InclusiveRangeExt for RangeInclusive<isize> {
-    fn contains_range(&self, other: &Self) -> bool {
-        self.contains(other.start()) && self.contains(other.end())
-    }
+pub struct Day15;
+
+impl Solution for Day15 {
+    const PROBLEM: Problem = P;
+    type Parsed = Scan;
+    type OutputPt1 = usize;
+    type OutputPt2 = isize;
 
-    fn overlaps(&self, other: &Self) -> bool {
-        self.contains(other.start()) || self.contains(other.end())
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
     }
 
-    fn is_adjacent(&self, other: &Self) -> bool {
-        self.start() == &(other.end() + 1) || self.end() == &(other.start() - 1)
+    fn pt1(scan: &Self::Parsed) -> Self::OutputPt1 {
+        pt1(&scan.sensors, scan.row)
     }
-}
 
-fn pt2(input: &str, x_range: RangeInclusive<isize>, y_range: RangeInclusive<isize>) -> isize {
-    // TODO: speed up lol
-    let sensors = parse(input);
-    let mut beacon = (0, 0);
-    let x_iter = x_range.collect::<Vec<isize>>();
-    let y_iter = y_range.collect::<Vec<isize>>();
-
-    for &x in x_iter.iter() {
-        let mut ys: Vec<RangeInclusive<isize>> = Vec::new();
-        for sensor in sensors.iter() {
-            if sensor.is_in_range((x, sensor.origin.1)) {
-                let delta = sensor.range as isize - (sensor.origin.0 - x).abs();
-                let low = (sensor.origin.1 - delta);
-                let high = (sensor.origin.1 + delta);
-                ys.push(low..=high)
-            }
-        }
-        ys.sort_by(|a, b| a.start().cmp(&b.start()));
-        ys = ys.iter().fold(
-            Vec::new(),
-            |mut acc: Vec<RangeInclusive<isize>>, next_range| {
-                if let Some(last) = acc.last() {
-                    if last.contains_range(&next_range) {
-                        // drop next_range
-                    } else if last.overlaps(&next_range) || last.is_adjacent(&next_range) {
-                        let last = acc.pop().unwrap();
-                        acc.push(*last.start()..=*next_range.end());
-                    } else {
-                        acc.push(next_range.clone())
-                    }
-                } else {
-                    acc.push(next_range.clone())
-                }
-                acc
-            },
-        );
-
-        if ys.len() > 1 {
-            println!("{x} {:?}", ys);
-            beacon = (x, ys[0].end() + 1);
-            println!("{:?}", beacon);
-            break;
-        }
+    /// the distress beacon's tuning frequency
+    fn pt2(scan: &Self::Parsed) -> Self::OutputPt2 {
+        scan.distress.x * 4000000 + scan.distress.y
     }
-    
-    println!("{:?}", beacon);
-    return beacon.0 * 4000000 + beacon.1;
 }
 
-fn parse(input: &str) -> Vec<Sensor> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
-}
+crate::examples!(Day15, pt1: [_1], pt2: [_1]);
 
 #[cfg(test)]
 mod tests {
@@ -157,8 +131,8 @@ mod tests {
     #[test]
     fn test_parse() {
         let input = read_to_string(P.example_path("_1")).expect("no such file");
-        let parsed = parse(&input);
-        for p in parsed {
+        let parsed = parse(&input).unwrap();
+        for p in parsed.sensors {
             println!("{:?}", p);
         }
     }
@@ -167,25 +141,30 @@ mod tests {
     fn pt1_example() {
         let input = read_to_string(P.example_path("_1")).expect("no such file");
         let target = 10;
-        assert_eq!(pt1(&input, target), 26);
+        assert_eq!(pt1(&parse(&input).unwrap().sensors, target), 26);
     }
 
     #[test]
-    fn pt1_input() {
-        let input = read_to_string(P.input_path()).expect("no such file");
-        let target = 2000000;
-        assert_eq!(pt1(&input, target), 5878678); // 5878678
+    fn pt2_example() {
+        let input = read_to_string(P.example_path("_1")).expect("no such file");
+        let sensors = parse(&input).unwrap().sensors;
+        assert_eq!(distress(&sensors, 20), Some(Point2::new(14, 11)));
     }
 
     #[test]
-    fn pt2_example() {
+    fn sizes() {
         let input = read_to_string(P.example_path("_1")).expect("no such file");
-        assert_eq!(pt2(&input, 0..=20, 0..=20), 56000011);
+        let scan = parse(&input).unwrap();
+        assert_eq!(scan.row, 10);
+        assert_eq!(scan.distress, Point2::new(14, 11));
+        assert_eq!(pt1(&scan.sensors, 11), 28);
     }
 
     #[test]
-    fn pt2_input() {
-        let input = read_to_string(P.input_path()).expect("no such file");
-        assert_eq!(pt2(&input, 0..=4000000, 0..=4000000), 11796491041245);
+    fn no_gap() {
+        assert!(matches!(
+            parse("Sensor at x=10, y=10: closest beacon is at x=10, y=30"),
+            Err(Error::ParseError(ParseError::InvalidSequence(_)))
+        ));
     }
 }
@@ -1,8 +1,156 @@
-/// TODO: bencher
-///
-/// options about which file to bench (default all)
-/// options for which year to bench (default all)
-/// 
-/// 
-/// 
-fn main(){}
+use aoc_rust::{registry, DynSolution};
+use clap::Parser;
+use std::{
+    cmp::Reverse,
+    fs::read_to_string,
+    hint::black_box,
+    process,
+    time::{Duration, Instant},
+};
+
+/// benchmark solutions against their inputs. e.g. `cargo benches 2021 15`
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// year to bench, defaults to every year
+    #[clap(index = 1, value_name = "year")]
+    year: Option<usize>,
+
+    /// day to bench, defaults to every day of the year
+    #[clap(index = 2, value_name = "day")]
+    day: Option<usize>,
+
+    /// number of timed runs of each step
+    #[clap(short, long, default_value_t = 20)]
+    samples: usize,
+}
+
+/// summary statistics over a set of timed runs
+#[derive(Debug, Clone, Copy)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    stddev: Duration,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let n = samples.len() as f64;
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / n;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// times `f` once per sample
+fn sample<T>(samples: usize, mut f: impl FnMut() -> T) -> Stats {
+    let timings = (0..samples)
+        .map(|_| {
+            let now = Instant::now();
+            black_box(f());
+            now.elapsed()
+        })
+        .collect();
+    Stats::new(timings)
+}
+
+fn print_row(label: &str, stats: &Stats) {
+    println!(
+        "{:<8} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?}",
+        label, stats.min, stats.median, stats.mean, stats.stddev
+    );
+}
+
+/// benches each step of a solution. returns the label and total median time,
+/// or `None` if the input couldn't be read or parsed
+fn bench(solution: &dyn DynSolution, samples: usize) -> Option<(String, Duration)> {
+    let problem = solution.problem();
+    let label = format!("{} day {:02}: {}", problem.year, problem.day, problem.name);
+    println!("🎄 {} 🎄", label);
+
+    let path = problem.input_path();
+    let input = match read_to_string(&path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!(
+                "Failed to read input \"{}\". {}",
+                path.as_ref().display(),
+                e
+            );
+            return None;
+        }
+    };
+    let parsed = match solution.parse_input(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Failed to parse input. {}", e.with_path(&path));
+            return None;
+        }
+    };
+
+    let parse = sample(samples, || solution.parse_input(&input));
+    let pt1 = sample(samples, || solution.run_pt1(parsed.as_ref()));
+    let pt2 = sample(samples, || solution.run_pt2(parsed.as_ref()));
+
+    println!(
+        "{:<8} {:>12} {:>12} {:>12} {:>12}",
+        "", "min", "median", "mean", "stddev"
+    );
+    print_row("parse", &parse);
+    print_row("part 1", &pt1);
+    print_row("part 2", &pt2);
+    println!();
+    Some((label, parse.median + pt1.median + pt2.median))
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.samples == 0 {
+        eprintln!("At least one sample is required");
+        process::exit(1);
+    }
+
+    let solutions: Vec<Box<dyn DynSolution>> = registry()
+        .into_iter()
+        .filter(|s| args.year.is_none_or(|year| s.problem().year == year))
+        .filter(|s| args.day.is_none_or(|day| s.problem().day == day))
+        .collect();
+    if solutions.is_empty() {
+        eprintln!("No solutions registered for the selection");
+        process::exit(1);
+    }
+
+    let mut totals: Vec<(String, Duration)> = solutions
+        .iter()
+        .filter_map(|s| bench(s.as_ref(), args.samples))
+        .collect();
+    if totals.len() < 2 {
+        return;
+    }
+
+    // most expensive first
+    totals.sort_by_key(|(_, median)| Reverse(*median));
+    let total: Duration = totals.iter().map(|(_, median)| *median).sum();
+    println!("--------------------------------");
+    println!("{:<40} {:>12} {:>8}", "problem", "median", "share");
+    for (label, median) in totals.iter() {
+        println!(
+            "{:<40} {:>12.2?} {:>7.1}%",
+            label,
+            median,
+            100.0 * median.as_secs_f64() / total.as_secs_f64()
+        );
+    }
+    println!("{:<40} {:>12.2?}", "total", total);
+}
@@ -1,7 +1,81 @@
-/// TODO: download input from day 
-/// dependencies: aoc_cli 
-///     - should i bother
-/// 
-/// 
+use aoc_rust::{client::Client, Problem};
+use clap::Parser;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    process,
+};
 
-fn main(){}
\ No newline at end of file
+/// download a puzzle input to `inputs/aoc{year}/inputs/day{day}.txt`. e.g. `cargo download 4 2022`.
+/// reads the session token from `AOC_SESSION` or `~/.adventofcode.session`,
+/// set `AOC_BASE_URL` to fetch from somewhere other than adventofcode.com
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// day to download
+    #[clap(index = 1, value_name = "day")]
+    day: usize,
+
+    /// year to download, defaults to 2022
+    #[clap(
+        default_value_t = 2022,
+        index = 2,
+        value_name = "year (defaults to 2022)"
+    )]
+    year: usize,
+}
+
+fn main() {
+    let args = Args::parse();
+    if !(1..=25).contains(&args.day) {
+        eprintln!("Day must be between 1 and 25");
+        process::exit(1);
+    }
+    let problem = Problem {
+        year: args.year,
+        day: args.day,
+        name: "",
+    };
+    let input_path = problem.input_path();
+    let path = input_path.as_ref();
+
+    // never clobber an existing input, checked before spending a request
+    if path.exists() {
+        eprintln!("Input \"{}\" already exists", path.display());
+        process::exit(1);
+    }
+
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    let input = match client.input(args.year, args.day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to download input. {}", e);
+            process::exit(1);
+        }
+    };
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Failed to create \"{}\". {}", parent.display(), e);
+            process::exit(1);
+        }
+    }
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(input.as_bytes()))
+    {
+        Ok(_) => println!("🎄 Downloaded input to \"{}\" 🎄", path.display()),
+        Err(e) => {
+            eprintln!("Failed to write input \"{}\". {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use aoc_rust::{
+    error::Error,
+    grid::Grid,
+    image::{self, write_png, Gif, Rgb},
+};
+use clap::Parser;
+use std::{
+    fs::{read_to_string, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+/// save a day's working as pictures. e.g. `cargo export 2022 14 sand.gif`.
+/// a `.gif` animates every step and a `.png` shows the last
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// year to export
+    #[clap(index = 1, value_name = "year")]
+    year: usize,
+
+    /// day to export
+    #[clap(index = 2, value_name = "day")]
+    day: usize,
+
+    /// file to write, ending in `.png` or `.gif`
+    #[clap(index = 3, value_name = "output")]
+    output: PathBuf,
+
+    /// pixels along each side of a cell
+    #[clap(long, default_value_t = 4)]
+    scale: usize,
+
+    /// milliseconds each frame of a gif is shown for
+    #[clap(long, default_value_t = 40)]
+    delay: u64,
+
+    /// with a png, write every step to `<name>_0001.png` onwards rather than only the last
+    #[clap(long)]
+    all: bool,
+
+    /// export an example, e.g. `_1`, rather than the input
+    #[clap(long)]
+    example: Option<String>,
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>, Error> {
+    Ok(BufWriter::new(File::create(path)?))
+}
+
+/// `sand.png` to `sand_0007.png`
+fn numbered(path: &Path, i: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}_{:04}.png", stem, i))
+}
+
+/// appends `image` to the gif, creating it the size of the first frame
+fn push_gif(
+    args: &Args,
+    gif: &mut Option<Gif<BufWriter<File>>>,
+    image: &Grid<Rgb>,
+) -> Result<(), Error> {
+    let gif = match gif {
+        Some(gif) => gif,
+        None => {
+            let delay = Duration::from_millis(args.delay);
+            let (width, height) = (image.width(), image.height());
+            gif.insert(Gif::new(
+                create(&args.output)?,
+                width,
+                height,
+                args.scale,
+                delay,
+            )?)
+        }
+    };
+    gif.push(image)
+}
+
+fn main() {
+    let args = Args::parse();
+    let export = image::find(args.year, args.day).unwrap_or_else(|| {
+        fail(format!(
+            "No export registered for {} day {}",
+            args.year, args.day
+        ))
+    });
+    let problem = export.problem();
+    let extension = args.output.extension().and_then(|e| e.to_str());
+    if !matches!(extension, Some("png" | "gif")) {
+        fail("The output should end in .png or .gif");
+    }
+
+    let path: PathBuf = match &args.example {
+        Some(id) => problem.example_path(id).as_ref().to_owned(),
+        None => problem.input_path().as_ref().to_owned(),
+    };
+    let input = read_to_string(&path).unwrap_or_else(|e| {
+        fail(format!(
+            "Failed to read input \"{}\". {}",
+            path.display(),
+            e
+        ))
+    });
+    let parsed = export
+        .parse_input(&input)
+        .unwrap_or_else(|e| fail(format!("Failed to parse input. {}", e.with_path(&path))));
+
+    // frames are written as they arrive, so a long simulation isn't held in memory
+    let mut frames = 0;
+    let mut gif = None;
+    let mut last = None;
+    let mut written = Ok(());
+    export.export(parsed.as_ref(), &mut |image| {
+        if written.is_err() {
+            return;
+        }
+        frames += 1;
+        written = match (extension, args.all) {
+            (Some("gif"), _) => push_gif(&args, &mut gif, &image),
+            (_, true) => create(&numbered(&args.output, frames))
+                .and_then(|out| write_png(out, &image, args.scale)),
+            (_, false) => {
+                last = Some(image);
+                Ok(())
+            }
+        };
+    });
+    // finishes the file
+    drop(gif);
+    if frames == 0 {
+        fail("Nothing to export");
+    }
+
+    let output = args.output.display();
+    // only a single png waits for the last frame
+    let written = match last {
+        Some(last) => create(&args.output)
+            .and_then(|out| write_png(out, &last, args.scale))
+            .map(|_| format!("wrote the last of {} frames to {}", frames, output)),
+        None if extension == Some("gif") => {
+            written.map(|_| format!("wrote {} frames to {}", frames, output))
+        }
+        None => written.map(|_| {
+            let first = numbered(&args.output, 1);
+            format!("wrote {} frames to {} onwards", frames, first.display())
+        }),
+    };
+    match written {
+        Ok(message) => println!("{}", message),
+        Err(e) => fail(format!("Failed to write \"{}\". {}", output, e)),
+    }
+}
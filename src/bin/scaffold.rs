@@ -1,8 +1,11 @@
+use aoc_rust::{answers::EXAMPLES_PATH, client::Client, error::ClientError, puzzle::Puzzle};
 use clap::Parser;
 /// scaffold to generate template. see: https://github.com/fspoettel/advent-of-code-rust
 use std::{
-    fs::{File, OpenOptions},
-    io::{Read, Write},
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
@@ -20,152 +23,549 @@ struct Args {
         value_name = "year (defaults to 2022)"
     )]
     year: u16,
+
+    /// solution template, one of lines, grid, chunks or single, or a path to a template file
+    #[clap(short, long, default_value = "lines")]
+    template: String,
+
+    /// puzzle name, fills `$NAME` in the template. defaults to the name on the puzzle page
+    #[clap(short, long)]
+    name: Option<String>,
+
+    /// puzzle page to take the name, examples and example answers from. a url or a saved
+    /// html file. with no value the day's page is fetched, or read from the cache of an earlier fetch
+    #[clap(short, long, num_args = 0..=1, default_missing_value = "")]
+    puzzle: Option<String>,
+
+    /// print the planned changes without making them
+    #[clap(long)]
+    dry_run: bool,
 }
 
-const TEMPLATE: &str = r###"use crate::Problem;
-/// problem: https://adventofcode.com/$YEAR/day/$DAY
-/// input: https://adventofcode.com/$YEAR/day/$DAY/input
+/// the templates shipped in `templates/`, selected by name
+const TEMPLATES: [&str; 4] = ["lines", "grid", "chunks", "single"];
 
-const P: Problem = Problem {
-    year: $YEAR,
-    day: $DAY,
-    name: "TODO",
-};
+const YEAR_TEMPLATE: &str = r###"pub mod day$PADDED_DAY;
+
+use crate::DynSolution;
 
-fn pt1(input: &str) -> usize {
-    let parsed = parse(input);
-    todo!()
+/// every solved day of the year
+pub fn solutions() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        Box::new(day$PADDED_DAY::Day$PADDED_DAY),
+    ]
 }
+"###;
 
-fn pt2(input: &str) -> usize {
-    let parsed = parse(input);
-    todo!()
+/// reads a template by name from `templates/`, or from a path
+fn load_template(template: &str) -> io::Result<String> {
+    if TEMPLATES.contains(&template) {
+        fs::read_to_string(format!("templates/{}.rs", template))
+    } else {
+        fs::read_to_string(template)
+    }
 }
 
-fn parse(input: &str) -> Vec<usize> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+/// where a fetched puzzle page is kept, so scaffolding again works offline
+fn cache_path(day: u8, year: u16) -> String {
+    format!("inputs/aoc{}/puzzles/day{:02}.html", year, day)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs::read_to_string;
+/// reads the puzzle page from `source`. returns the page and whether it was fetched
+fn load_puzzle(source: &str, day: u8, year: u16) -> Result<(String, bool), ClientError> {
+    let client = || Client::from_env().unwrap_or_else(|_| Client::anonymous());
+    if source.starts_with("http://") || source.starts_with("https://") {
+        Ok((client().get(source)?, true))
+    } else if !source.is_empty() {
+        Ok((fs::read_to_string(source)?, false))
+    } else if Path::new(&cache_path(day, year)).exists() {
+        Ok((fs::read_to_string(cache_path(day, year))?, false))
+    } else {
+        Ok((client().puzzle(year.into(), day.into())?, true))
+    }
+}
 
-    #[test]
-    fn pt1_example() {
-        let input = read_to_string(P.example_path("_1")).expect("no such file");
-        assert_eq!(pt1(&input), 0);
+/// a change to the tree, planned up front so `--dry-run` can print it
+enum Change {
+    CreateDir(String),
+    CreateFile(String, String),
+    UpdateFile {
+        path: String,
+        contents: String,
+        added: Vec<String>,
+    },
+}
+
+impl Change {
+    fn apply(&self) -> io::Result<()> {
+        match self {
+            Change::CreateDir(path) => fs::create_dir_all(path),
+            Change::CreateFile(path, contents) => OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)?
+                .write_all(contents.as_bytes()),
+            Change::UpdateFile { path, contents, .. } => fs::write(path, contents),
+        }
     }
+}
 
-    #[test]
-    fn pt1_input() {
-        let input = read_to_string(P.input_path()).expect("no such file");
-        assert_eq!(pt1(&input), 0);
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::CreateDir(path) => write!(f, "create directory \"{}\"", path),
+            Change::CreateFile(path, _) => write!(f, "create file \"{}\"", path),
+            Change::UpdateFile { path, added, .. } => {
+                write!(f, "update file \"{}\"", path)?;
+                for line in added {
+                    write!(f, "\n    + {}", line.trim())?;
+                }
+                Ok(())
+            }
+        }
     }
+}
 
-    #[test]
-    fn pt2_example() {
-        let input = read_to_string(P.example_path("_1")).expect("no such file");
-        assert_eq!(pt2(&input), 0);
+/// splits a one line `vec![a, b]`, as rustfmt leaves a short registry, into a line per item
+fn expand_vec(line: &str) -> Vec<String> {
+    let indent = &line[..line.len() - line.trim_start().len()];
+    match line
+        .trim()
+        .strip_prefix("vec![")
+        .and_then(|l| l.strip_suffix(']'))
+    {
+        Some(items) if !items.trim().is_empty() => std::iter::once(format!("{}vec![", indent))
+            .chain(
+                items
+                    .split(',')
+                    .filter(|item| !item.trim().is_empty())
+                    .map(|item| format!("{}    {},", indent, item.trim())),
+            )
+            .chain(std::iter::once(format!("{}]", indent)))
+            .collect(),
+        _ => vec![line.to_owned()],
     }
+}
 
-    #[test]
-    fn pt2_input() {
-        let input = read_to_string(P.input_path()).expect("no such file");
-        assert_eq!(pt2(&input), 0);
+/// inserts `line` among the existing lines matching `peer`, keeping them sorted.
+/// with no peers the line goes after the first line matching `anchor`, or at the top.
+/// returns `None` if the line is already there
+fn insert_sorted(
+    source: &str,
+    line: &str,
+    peer: impl Fn(&str) -> bool,
+    anchor: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let mut lines: Vec<String> = source.lines().map(str::to_owned).collect();
+    // split the one line `vec![..]` the line goes into, leaving any others alone
+    let holding = |test: &dyn Fn(&str) -> bool| {
+        lines.iter().position(|l| {
+            let items = expand_vec(l);
+            items.len() > 1 && items.iter().any(|item| test(item.trim()))
+        })
+    };
+    if let Some(i) = holding(&peer).or_else(|| holding(&anchor)) {
+        let items = expand_vec(&lines[i]);
+        lines.splice(i..=i, items);
     }
+    if lines.iter().any(|l| l.trim() == line) {
+        return None;
+    }
+    let peers: Vec<usize> = (0..lines.len())
+        .filter(|&i| peer(lines[i].trim()))
+        .collect();
+    let (index, indent) = match (peers.first(), peers.last()) {
+        (Some(&first), Some(&last)) => {
+            let index = peers
+                .iter()
+                .copied()
+                .find(|&i| lines[i].trim() > line)
+                .unwrap_or(last + 1);
+            let indent = lines[first].len() - lines[first].trim_start().len();
+            (index, indent)
+        }
+        _ => match lines.iter().position(|l| anchor(l.trim())) {
+            Some(i) => (i + 1, lines[i].len() - lines[i].trim_start().len() + 4),
+            None => (0, 0),
+        },
+    };
+    lines.insert(index, format!("{}{}", " ".repeat(indent), line));
+
+    // keep the file's own line endings
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut updated = lines.join(newline);
+    if source.ends_with('\n') {
+        updated.push_str(newline);
+    }
+    Some(updated)
 }
-"###;
 
-/// creates a file with write access. returns an error if the file already exists.
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+/// an answer as toml, a number if it is one and otherwise a string
+fn toml_value(answer: &str) -> toml::Value {
+    match answer.parse::<i64>() {
+        Ok(n) => toml::Value::Integer(n),
+        Err(_) => toml::Value::String(answer.to_owned()),
+    }
 }
 
-/// gets a file with append access. returns an error if the file already exists.
-fn append_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().append(true).open(path)
+/// adds a `[year.day]` table holding `lines` to a toml file of tables ordered by year then day,
+/// such as examples.toml. returns `None` if the table is already there
+fn insert_table(source: &str, year: u16, day: u8, lines: &[String]) -> Option<String> {
+    let header = |line: &str| -> Option<(u16, u8)> {
+        let (y, d) = line
+            .trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .split_once('.')?;
+        Some((y.parse().ok()?, d.parse().ok()?))
+    };
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let source = source.replace("\r\n", "\n");
+    let mut tables: Vec<&str> = source
+        .trim_end()
+        .split("\n\n")
+        .filter(|t| !t.is_empty())
+        .collect();
+    if tables
+        .iter()
+        .any(|t| t.lines().any(|l| header(l) == Some((year, day))))
+    {
+        return None;
+    }
+    // a table goes before the first later one, keeping any comment above it
+    let later = tables
+        .iter()
+        .position(|t| t.lines().filter_map(header).next() > Some((year, day)));
+    let table = format!("[{}.{}]\n{}", year, day, lines.join("\n"));
+    tables.insert(later.unwrap_or(tables.len()), &table);
+    Some((tables.join("\n\n") + "\n").replace('\n', newline))
+}
+
+/// plans adding the `[year.day]` table to the toml file at `path`, if it's missing
+fn plan_table(
+    changes: &mut Vec<Change>,
+    path: &str,
+    year: u16,
+    day: u8,
+    lines: &[String],
+) -> io::Result<()> {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    if let Some(contents) = insert_table(&source, year, day, lines) {
+        changes.push(Change::UpdateFile {
+            path: path.to_owned(),
+            contents,
+            added: lines.to_vec(),
+        });
+    }
+    Ok(())
+}
+
+/// plans an insertion of `line` into the file at `path`, if it's missing
+fn plan_insert(
+    changes: &mut Vec<Change>,
+    path: &str,
+    line: &str,
+    peer: impl Fn(&str) -> bool,
+    anchor: impl Fn(&str) -> bool,
+) -> io::Result<()> {
+    // an earlier change may already have touched the file
+    let earlier = changes
+        .iter()
+        .position(|c| matches!(c, Change::UpdateFile { path: p, .. } if p == path));
+    let (source, mut added) = match earlier.map(|i| changes.remove(i)) {
+        Some(Change::UpdateFile {
+            contents, added, ..
+        }) => (contents, added),
+        _ => (fs::read_to_string(path)?, Vec::new()),
+    };
+    let contents = match insert_sorted(&source, line, peer, anchor) {
+        Some(contents) => {
+            added.push(line.to_owned());
+            contents
+        }
+        None if added.is_empty() => return Ok(()),
+        None => source,
+    };
+    changes.push(Change::UpdateFile {
+        path: path.to_owned(),
+        contents,
+        added,
+    });
+    Ok(())
+}
+
+/// everything needed to add a day, skipping whatever already exists
+fn plan(
+    day: u8,
+    year: u16,
+    name: Option<&str>,
+    template: &str,
+    page: Option<(&str, bool)>,
+) -> io::Result<Vec<Change>> {
+    let puzzle = page
+        .map(|(html, _)| Puzzle::from_html(html))
+        .unwrap_or_default();
+    let name = name.or(puzzle.name.as_deref()).unwrap_or("TODO");
+    // the template's `examples!` tests `_1` for each part the page gives an answer to
+    let listed = |part: usize| match puzzle.answers.get(part) {
+        Some(Some(_)) => "_1",
+        _ => "",
+    };
+    let padded_day = format!("{:02}", day);
+    let day_str = format!("day{}", padded_day); // e.g. day04
+    let year_str = format!("aoc{}", year); // e.g. aoc2022
+    let fill = |template: &str| {
+        template
+            .replace("$PADDED_DAY", &padded_day)
+            .replace("$DAY", &day.to_string())
+            .replace("$YEAR", &year.to_string())
+            .replace("$NAME", name)
+            .replace("$PT1_EXAMPLES", listed(0))
+            .replace("$PT2_EXAMPLES", listed(1))
+    };
+
+    let mut changes = Vec::new();
+    for dir in [
+        format!("src/{}", year_str),
+        format!("inputs/{}/inputs", year_str),
+        format!("inputs/{}/examples", year_str),
+    ] {
+        if !Path::new(&dir).is_dir() {
+            changes.push(Change::CreateDir(dir));
+        }
+    }
+
+    let src_path = format!("src/{}/{}.rs", year_str, day_str);
+    if !Path::new(&src_path).exists() {
+        changes.push(Change::CreateFile(src_path, fill(template)));
+    }
+    // the answers the page gives, for the template's `examples!` tests to check `_1` against
+    let mut table: Vec<String> = puzzle
+        .answers
+        .iter()
+        .zip(1..)
+        .filter_map(|(answer, part)| {
+            Some(format!("{}._1 = {}", part, toml_value(answer.as_ref()?)))
+        })
+        .collect();
+    // one example per code block on the page, or an empty "_1" to fill in by hand
+    let examples = match puzzle.examples.len() {
+        0 => vec![String::new()],
+        _ => puzzle.examples.clone(),
+    };
+    let mut skip = Vec::new();
+    for (i, example) in examples.into_iter().enumerate() {
+        let id = format!("_{}", i + 1);
+        let example_path = format!("inputs/{}/examples/{}{}.txt", year_str, day_str, id);
+        if !Path::new(&example_path).exists() {
+            changes.push(Change::CreateFile(example_path, example));
+            // an example without answers waits in `skip` until they're recorded by hand
+            if i > 0 || table.is_empty() {
+                skip.push(id);
+            }
+        }
+    }
+    if !skip.is_empty() {
+        table.push(format!("skip = {}", toml::Value::from(skip)));
+    }
+    if !table.is_empty() {
+        plan_table(&mut changes, EXAMPLES_PATH, year, day, &table)?;
+    }
+    if let Some((html, true)) = page {
+        let cache = cache_path(day, year);
+        if !Path::new(&cache).exists() {
+            let dir = format!("inputs/{}/puzzles", year_str);
+            if !Path::new(&dir).is_dir() {
+                changes.push(Change::CreateDir(dir));
+            }
+            changes.push(Change::CreateFile(cache, html.to_owned()));
+        }
+    }
+
+    // register the day with its year, and a new year with the crate
+    let mod_file_path = format!("src/{}.rs", year_str);
+    if Path::new(&mod_file_path).exists() {
+        plan_insert(
+            &mut changes,
+            &mod_file_path,
+            &format!("pub mod {};", day_str),
+            |l| l.starts_with("pub mod day"),
+            |_| false,
+        )?;
+        plan_insert(
+            &mut changes,
+            &mod_file_path,
+            &format!("Box::new({}::Day{}),", day_str, padded_day),
+            |l| l.starts_with("Box::new(day"),
+            |l| l == "vec![",
+        )?;
+    } else {
+        changes.push(Change::CreateFile(mod_file_path, fill(YEAR_TEMPLATE)));
+        plan_insert(
+            &mut changes,
+            "src/lib.rs",
+            &format!("pub mod {};", year_str),
+            |l| l.starts_with("pub mod aoc"),
+            |_| false,
+        )?;
+        plan_insert(
+            &mut changes,
+            "src/lib.rs",
+            &format!("solutions.extend({}::solutions());", year_str),
+            |l| l.starts_with("solutions.extend(aoc"),
+            |l| l.starts_with("let mut solutions"),
+        )?;
+    }
+    Ok(changes)
 }
 
 fn main() {
     let args = Args::parse();
     let day = args.day;
     let year = args.year;
-    let day_str = format!("day{:02}", day); // e.g. day04
-
-    let input_path = format!("inputs/aoc{}/inputs/{}.txt", year, &day_str);
-    let example_path = format!("inputs/aoc{}/examples/{}_1.txt", year, &day_str); // defaults to "_1"
-    let src_path = format!("src/aoc{}/{}.rs", year, &day_str);
-    let mod_file_path = format!("src/aoc{}.rs", year);
+    if !(1..=25).contains(&day) {
+        eprintln!("Day must be between 1 and 25");
+        process::exit(1);
+    }
 
-    // make src file
-    match create_file(&src_path) {
+    let template = match load_template(&args.template) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create src file at \"{}\". {}", &src_path, e);
+            eprintln!("Failed to read template \"{}\". {}", &args.template, e);
             process::exit(1);
         }
-        Ok(mut file) => {
-            match file.write_all(
-                TEMPLATE
-                    .replace("$DAY", &day.to_string())
-                    .replace("$YEAR", &year.to_string())
-                    .as_bytes(),
-            ) {
-                Ok(_) => {
-                    println!("Created module file \"{}\"", &src_path);
-                }
-                Err(e) => {
-                    eprintln!("Failed to write module contents: {}", e);
-                    process::exit(1);
-                }
-            }
-        }
     };
-
-    // update mod file
-    match append_file(&mod_file_path) {
-        Ok(mut f) => {
-            f.write(format!("pub mod {};\n", &day_str).as_bytes());
-            println!(
-                "Updated mod file \"{}\" with: \"pub mod {};\"",
-                &mod_file_path, &day_str
-            );
-        }
-        Err(e) => {
-            eprintln!(
-                "Failed to append to mod file: \"{}\". {}",
-                &mod_file_path, e
-            );
+    let page = match args.puzzle.as_deref().map(|s| load_puzzle(s, day, year)) {
+        None => None,
+        Some(Ok(page)) => Some(page),
+        Some(Err(e)) => {
+            eprintln!("Failed to load puzzle page. {}", e);
             process::exit(1);
         }
-    }
-
-    // make empty input file
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+    };
+    let page = page
+        .as_ref()
+        .map(|(html, fetched)| (html.as_str(), *fetched));
+    let changes = match plan(day, year, args.name.as_deref(), &template, page) {
+        Ok(changes) => changes,
         Err(e) => {
-            eprintln!("Failed to create input file: \"{}\". {}", &input_path, e);
+            eprintln!("Failed to plan scaffold. {}", e);
             process::exit(1);
         }
+    };
+    if changes.is_empty() {
+        println!("🎄 Nothing to do, day{:02} is already scaffolded 🎄", day);
+        return;
     }
 
-    // make empty example file 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    if args.dry_run {
+        println!("Planned changes:");
+        for change in changes.iter() {
+            println!("  {}", change);
         }
-        Err(e) => {
-            eprintln!(
-                "Failed to create example file: \"{}\". {}",
-                &example_path, e
-            );
-            process::exit(1);
+        return;
+    }
+    for change in changes.iter() {
+        match change.apply() {
+            Ok(_) => println!("Did {}", change),
+            Err(e) => {
+                eprintln!("Failed to {}. {}", change, e);
+                process::exit(1);
+            }
         }
     }
 
     println!("--------------------------------");
-    println!("🎄 Scaffold created for {} 🎄", &day_str);
+    println!("🎄 Scaffold created for day{:02} 🎄", day);
     println!("--------------------------------");
+    // an empty input would only get in the way of `download`
+    println!("Fetch the input with `cargo download {} {}`", day, year);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str =
+        "crate::examples!(Day$PADDED_DAY, pt1: [$PT1_EXAMPLES], pt2: [$PT2_EXAMPLES]);";
+
+    const PAGE: &str = r#"<article class="day-desc"><h2>--- Day 25: Full of Hot Air ---</h2>
+<pre><code>1=-0-2
+</code></pre>
+<p>The sum is <code><em>2=-1=0</em></code>.</p>
+<pre><code>1=
+</code></pre>
+<pre><code>2-
+</code></pre>
+</article>"#;
+
+    /// the examples.toml lines a plan adds
+    fn table(changes: &[Change]) -> Vec<String> {
+        changes
+            .iter()
+            .find_map(|change| match change {
+                Change::UpdateFile { path, added, .. } if path == EXAMPLES_PATH => {
+                    Some(added.clone())
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    fn created(changes: &[Change]) -> Vec<&str> {
+        changes
+            .iter()
+            .filter_map(|change| match change {
+                Change::CreateFile(path, _) => Some(path.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn multi_block_page() {
+        let changes = plan(25, 2022, None, TEMPLATE, Some((PAGE, false))).unwrap();
+        let source = changes.iter().find_map(|change| match change {
+            Change::CreateFile(path, contents) if path == "src/aoc2022/day25.rs" => {
+                Some(contents.as_str())
+            }
+            _ => None,
+        });
+        assert_eq!(source, Some("crate::examples!(Day25, pt1: [_1], pt2: []);"));
+        let examples: Vec<&str> = created(&changes)
+            .into_iter()
+            .filter(|path| path.starts_with("inputs/aoc2022/examples/"))
+            .collect();
+        assert_eq!(
+            examples,
+            vec![
+                "inputs/aoc2022/examples/day25_1.txt",
+                "inputs/aoc2022/examples/day25_2.txt",
+                "inputs/aoc2022/examples/day25_3.txt",
+            ]
+        );
+        assert_eq!(
+            table(&changes),
+            vec!["1._1 = \"2=-1=0\"", "skip = [\"_2\", \"_3\"]"]
+        );
+    }
+
+    #[test]
+    fn no_page() {
+        let changes = plan(25, 2022, None, TEMPLATE, None).unwrap();
+        assert!(created(&changes).contains(&"inputs/aoc2022/examples/day25_1.txt"));
+        assert_eq!(table(&changes), vec!["skip = [\"_1\"]"]);
+    }
 }
@@ -1,4 +1,106 @@
-/// TODO: 
-/// add submit option?
-/// 
-fn main(){}
+use aoc_rust::{
+    client::{Client, Verdict},
+    find,
+    history::{Attempt, History, DEFAULT_PATH},
+};
+use clap::Parser;
+use std::{fs::read_to_string, path::PathBuf, process};
+
+/// solve a part and submit the answer. e.g. `cargo submit 2022 4 1`.
+/// reads the session token from `AOC_SESSION` or `~/.adventofcode.session`,
+/// set `AOC_BASE_URL` to submit somewhere other than adventofcode.com
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// year to submit
+    #[clap(index = 1, value_name = "year")]
+    year: usize,
+
+    /// day to submit
+    #[clap(index = 2, value_name = "day")]
+    day: usize,
+
+    /// part to submit, 1 or 2
+    #[clap(index = 3, value_name = "part", value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// file recording every submitted answer
+    #[clap(long, default_value = DEFAULT_PATH)]
+    history: PathBuf,
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn main() {
+    let args = Args::parse();
+    let solution = find(args.year, args.day).unwrap_or_else(|| {
+        fail(format!(
+            "No solution registered for {} day {}",
+            args.year, args.day
+        ))
+    });
+    let problem = solution.problem();
+    println!(
+        "🎄 {} day {:02}: {} 🎄",
+        problem.year, problem.day, problem.name
+    );
+
+    let path = problem.input_path();
+    let input = read_to_string(&path).unwrap_or_else(|e| {
+        fail(format!(
+            "Failed to read input \"{}\". {}",
+            path.as_ref().display(),
+            e
+        ))
+    });
+    let parsed = solution
+        .parse_input(&input)
+        .unwrap_or_else(|e| fail(format!("Failed to parse input. {}", e.with_path(&path))));
+    let answer = match args.part {
+        1 => solution.run_pt1(parsed.as_ref()),
+        _ => solution.run_pt2(parsed.as_ref()),
+    };
+    println!("part {}: {}", args.part, answer);
+    if answer.contains('\n') {
+        fail("Multi line answers have to be read and submitted by hand");
+    }
+
+    let mut history = History::load(&args.history).unwrap_or_else(|e| {
+        fail(format!(
+            "Failed to read history \"{}\". {}",
+            args.history.display(),
+            e
+        ))
+    });
+    if let Err(e) = history.check(args.year, args.day, args.part, &answer) {
+        fail(format!("Not submitting, {}", e));
+    }
+
+    let client = Client::from_env().unwrap_or_else(|e| fail(e));
+    let verdict = client
+        .submit(args.year, args.day, args.part, &answer)
+        .unwrap_or_else(|e| fail(format!("Failed to submit answer. {}", e)));
+    let attempt = Attempt {
+        year: args.year,
+        day: args.day,
+        part: args.part,
+        answer,
+        verdict,
+    };
+    if let Err(e) = history.record(attempt) {
+        eprintln!("Failed to record attempt. {}", e);
+    }
+
+    match verdict {
+        Verdict::Right => println!("⭐ That's the right answer ⭐"),
+        Verdict::Wrong => fail("That's not the right answer"),
+        Verdict::TooHigh => fail("That's not the right answer, it's too high"),
+        Verdict::TooLow => fail("That's not the right answer, it's too low"),
+        Verdict::RateLimited(Some(wait)) => fail(format!("Rate limited, wait {:?}", wait)),
+        Verdict::RateLimited(None) => fail("Rate limited"),
+        Verdict::WrongLevel => fail("Wrong level, the part is solved or not unlocked yet"),
+    }
+}
@@ -0,0 +1,119 @@
+use aoc_rust::{
+    answers::{verify, Answers, Check, Outcome, DEFAULT_PATH, EXAMPLES_PATH},
+    registry, DynSolution,
+};
+use clap::Parser;
+use std::{path::PathBuf, process};
+
+/// check solutions against the recorded answer for every input on disk. e.g. `cargo verify 2022`.
+/// with `--examples` the examples are checked instead
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// year to verify, defaults to every year
+    #[clap(index = 1, value_name = "year")]
+    year: Option<usize>,
+
+    /// day to verify, defaults to every day of the year
+    #[clap(index = 2, value_name = "day")]
+    day: Option<usize>,
+
+    /// check the examples rather than the inputs
+    #[clap(long)]
+    examples: bool,
+
+    /// file recording the answer to each input, defaults to the inputs' or the examples' answers
+    #[clap(long)]
+    answers: Option<PathBuf>,
+}
+
+fn main() {
+    let args = Args::parse();
+    let path = args.answers.unwrap_or_else(|| match args.examples {
+        true => EXAMPLES_PATH.into(),
+        false => DEFAULT_PATH.into(),
+    });
+    let answers = Answers::load(&path).unwrap_or_else(|e| {
+        eprintln!("Failed to read answers \"{}\". {}", path.display(), e);
+        process::exit(1);
+    });
+
+    let solutions: Vec<Box<dyn DynSolution>> = registry()
+        .into_iter()
+        .filter(|s| args.year.is_none_or(|year| s.problem().year == year))
+        .filter(|s| args.day.is_none_or(|day| s.problem().day == day))
+        .collect();
+    if solutions.is_empty() {
+        eprintln!("No solutions registered for the selection");
+        process::exit(1);
+    }
+
+    println!("year   day  input            part 1   part 2");
+    let mut checks: Vec<Check> = Vec::new();
+    let mut no_input = 0;
+    for solution in solutions.iter() {
+        let problem = solution.problem();
+        let files = match args.examples {
+            true => problem.examples(),
+            false => problem.inputs(),
+        };
+        let day_checks = verify(solution.as_ref(), &answers, files);
+        for check in day_checks.iter() {
+            println!(
+                "{:<6} {:<4} {:<16} {:<8} {}",
+                check.year,
+                format!("{:02}", check.day),
+                check.input,
+                check.parts[0],
+                check.parts[1]
+            );
+        }
+        // answers recorded for someone else's input
+        no_input += answers
+            .inputs(problem.year, problem.day)
+            .into_iter()
+            .filter(|id| !day_checks.iter().any(|c| c.input == *id))
+            .count();
+        checks.extend(day_checks);
+    }
+
+    let outcomes = || checks.iter().flat_map(|c| c.parts.iter());
+    let count = |f: fn(&Outcome) -> bool| outcomes().filter(|o| f(o)).count();
+    let failed = count(|o| matches!(o, Outcome::Fail { .. } | Outcome::Error(_)));
+    println!("--------------------------------");
+    println!(
+        "{} passed, {} failed, {} missing",
+        count(|o| *o == Outcome::Pass),
+        failed,
+        count(|o| *o == Outcome::Missing)
+    );
+    if no_input > 0 {
+        println!("skipped {} recorded inputs that aren't on disk", no_input);
+    }
+
+    for check in checks.iter() {
+        for (part, outcome) in check.parts.iter().enumerate() {
+            let label = format!(
+                "{} day {:02} part {} ({})",
+                check.year,
+                check.day,
+                part + 1,
+                check.input
+            );
+            match outcome {
+                Outcome::Fail { expected, found } if !expected.contains('\n') => {
+                    eprintln!("{}: expected {} but found {}", label, expected, found)
+                }
+                Outcome::Fail { expected, found } => {
+                    eprintln!("{}: expected\n{}\nbut found\n{}", label, expected, found)
+                }
+                // both parts share the one parse error
+                Outcome::Error(e) if part == 0 => eprintln!("{}: {}", label, e),
+                _ => {}
+            }
+        }
+    }
+    if failed > 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,181 @@
+//! numbers packed into a stream of bits, most significant bit first
+
+use crate::error::ParseError;
+
+/// reads fields of any width from a byte buffer
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        BitReader { bytes, pos: 0 }
+    }
+
+    /// how many bits have been read so far
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.pos
+    }
+
+    /// the next `n` bits as a number. `n` can be at most 64
+    pub fn read_bits(&mut self, n: usize) -> Result<u64, ParseError> {
+        assert!(n <= 64, "can't read {} bits into a u64", n);
+        if n > self.remaining() {
+            return Err(ParseError::UnexpectedEnd);
+        }
+        let mut value = 0;
+        let mut n = n;
+        while n > 0 {
+            // take as much of the current byte as is wanted
+            let offset = self.pos % 8;
+            let take = n.min(8 - offset);
+            let byte = self.bytes[self.pos / 8] as u64;
+            let bits = (byte >> (8 - offset - take)) & ((1 << take) - 1);
+            value = value << take | bits;
+            self.pos += take;
+            n -= take;
+        }
+        Ok(value)
+    }
+
+    pub fn read_bit(&mut self) -> Result<bool, ParseError> {
+        Ok(self.read_bits(1)? == 1)
+    }
+}
+
+/// the counterpart to [`BitReader`], appending fields of any width
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// how many bits have been written
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// the low `n` bits of `value`. `n` can be at most 64
+    pub fn write_bits(&mut self, value: u64, n: usize) {
+        assert!(n <= 64, "can't write {} bits from a u64", n);
+        for i in (0..n).rev() {
+            self.write_bit(value >> i & 1 == 1);
+        }
+    }
+
+    pub fn write_bit(&mut self, bit: bool) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+        if bit {
+            *self.bytes.last_mut().unwrap() |= 0x80 >> (self.len % 8);
+        }
+        self.len += 1;
+    }
+
+    /// appends everything written to `other`
+    pub fn append(&mut self, other: &BitWriter) {
+        let mut reader = BitReader::new(&other.bytes);
+        for _ in 0..other.len {
+            self.write_bit(reader.read_bit().unwrap());
+        }
+    }
+
+    /// the bits written so far, with the last byte padded with zeros
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// two hex digits to a byte. an odd digit out fills the high half of the last byte
+pub fn decode_hex(hex: &str) -> Result<Vec<u8>, ParseError> {
+    let nibbles = hex
+        .chars()
+        .map(|c| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(ParseError::UnexpectedChar(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(nibbles
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+        .collect())
+}
+
+/// upper case, two digits per byte
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_across_bytes() {
+        let bytes = decode_hex("D2FE28").unwrap();
+        assert_eq!(bytes, [0xD2, 0xFE, 0x28]);
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(reader.read_bits(3).unwrap(), 6);
+        assert_eq!(reader.read_bits(3).unwrap(), 4);
+        assert!(reader.read_bit().unwrap());
+        assert_eq!(reader.read_bits(4).unwrap(), 0b0111);
+        assert_eq!(reader.pos(), 11);
+        assert_eq!(reader.read_bits(10).unwrap(), 0b11_1100_0101);
+        assert_eq!(reader.remaining(), 3);
+        assert!(matches!(
+            reader.read_bits(4),
+            Err(ParseError::UnexpectedEnd)
+        ));
+        assert_eq!(reader.read_bits(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(decode_hex("a0F").unwrap(), [0xA0, 0xF0]);
+        assert!(matches!(
+            decode_hex("0G"),
+            Err(ParseError::UnexpectedChar('G'))
+        ));
+        assert_eq!(encode_hex(&[0x0A, 0xFF]), "0AFF");
+        assert_eq!(decode_hex(&encode_hex(&[1, 2, 254])).unwrap(), [1, 2, 254]);
+    }
+
+    #[test]
+    fn write_then_read() {
+        let fields = [(5, 3), (0, 1), (u64::MAX, 64), (1234, 11), (1, 1)];
+        let mut writer = BitWriter::new();
+        for (value, n) in fields {
+            writer.write_bits(value, n);
+        }
+        let mut tail = BitWriter::new();
+        tail.write_bits(0b101, 3);
+        writer.append(&tail);
+        assert_eq!(writer.len(), 83);
+
+        let bytes = writer.into_bytes();
+        assert_eq!(bytes.len(), 11);
+        let mut reader = BitReader::new(&bytes);
+        for (value, n) in fields {
+            assert_eq!(reader.read_bits(n).unwrap(), value);
+        }
+        assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+        assert_eq!(reader.remaining(), 5);
+    }
+}
@@ -0,0 +1,313 @@
+//! a minimal client for adventofcode.com, shared by the `download` and `submit` binaries
+use crate::error::{ClientError, ParseError};
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::PathBuf,
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/FOMX/aoc_rust";
+/// minimum time between requests, shared by every run of the binaries
+const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// how adventofcode.com judged a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// answered too recently, with the wait if the response gave one
+    RateLimited(Option<Duration>),
+    /// the part was already solved, or part 2 isn't unlocked yet
+    WrongLevel,
+}
+
+impl Verdict {
+    /// reads the verdict out of the `<article>` of an answer response
+    pub fn from_response(html: &str) -> Option<Self> {
+        let article = article(html);
+        let verdict = if article.contains("That's the right answer") {
+            Verdict::Right
+        } else if article.contains("answer too recently") {
+            Verdict::RateLimited(wait(article))
+        } else if article.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if article.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if article.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if article.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            return None;
+        };
+        Some(verdict)
+    }
+}
+
+/// the main text of a page, or the whole page if it has no `<article>`
+fn article(html: &str) -> &str {
+    html.split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article)
+}
+
+/// parses the "You have 1m 5s left to wait" part of a rate limited response
+fn wait(article: &str) -> Option<Duration> {
+    let (_, rest) = article.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .map(|part| {
+            if let Some(n) = part.strip_suffix('m') {
+                n.parse::<u64>().ok()?.checked_mul(60)
+            } else {
+                part.strip_suffix('s')?.parse().ok()
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::RateLimited(_) => "rate limited",
+            Verdict::WrongLevel => "wrong level",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Verdict::Right),
+            "wrong" => Ok(Verdict::Wrong),
+            "too high" => Ok(Verdict::TooHigh),
+            "too low" => Ok(Verdict::TooLow),
+            "rate limited" => Ok(Verdict::RateLimited(None)),
+            "wrong level" => Ok(Verdict::WrongLevel),
+            _ => Err(ParseError::FromStrError),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+            min_interval: MIN_INTERVAL,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// the base url comes from `AOC_BASE_URL`, defaulting to adventofcode.com.
+    /// the session token comes from `AOC_SESSION` or `~/.adventofcode.session`
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => fs::read_to_string(session_path())
+                .map_err(|_| ClientError::MissingSession(session_path().display().to_string()))?,
+        };
+        Ok(Self::new(&base_url(), &session))
+    }
+
+    /// like `from_env` without a session, which is enough for puzzle pages
+    pub fn anonymous() -> Self {
+        Self::new(&base_url(), "")
+    }
+
+    /// wait at least `min_interval` between requests. zero disables throttling
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// the personal puzzle input for a day
+    pub fn input(&self, year: usize, day: usize) -> Result<String, ClientError> {
+        self.get(&format!("{}/{}/day/{}/input", self.base_url, year, day))
+    }
+
+    /// the puzzle page for a day. part 2 is only on it once part 1 is solved
+    pub fn puzzle(&self, year: usize, day: usize) -> Result<String, ClientError> {
+        self.get(&format!("{}/{}/day/{}", self.base_url, year, day))
+    }
+
+    /// any page, sending the session if there is one
+    pub fn get(&self, url: &str) -> Result<String, ClientError> {
+        self.throttle()?;
+        let mut request = self.agent.get(url);
+        if !self.session.is_empty() {
+            request = request.set("Cookie", &format!("session={}", self.session));
+        }
+        Ok(request.call().map_err(Box::new)?.into_string()?)
+    }
+
+    /// posts an answer to a part and reads back the verdict
+    pub fn submit(
+        &self,
+        year: usize,
+        day: usize,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, ClientError> {
+        self.throttle()?;
+        let response = self
+            .agent
+            .post(&format!("{}/{}/day/{}/answer", self.base_url, year, day))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(Box::new)?;
+        let html = response.into_string()?;
+        Verdict::from_response(&html)
+            .ok_or_else(|| ClientError::UnexpectedResponse(article(&html).to_owned()))
+    }
+
+    /// sleeps until `min_interval` has passed since the last request from any run
+    fn throttle(&self) -> Result<(), ClientError> {
+        if self.min_interval.is_zero() {
+            return Ok(());
+        }
+        let stamp = env::temp_dir().join("aoc_rust_last_request");
+        if let Ok(elapsed) = fs::metadata(&stamp)
+            .and_then(|m| m.modified())
+            .map(|modified| {
+                SystemTime::now()
+                    .duration_since(modified)
+                    .unwrap_or_default()
+            })
+        {
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        fs::write(&stamp, "")?;
+        Ok(())
+    }
+}
+
+fn base_url() -> String {
+    env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned())
+}
+
+fn session_path() -> PathBuf {
+    let home = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .unwrap_or_default();
+    PathBuf::from(home).join(".adventofcode.session")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+    };
+
+    /// serves a single response and returns the request line, headers and body it received
+    fn stub_server(body: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut request: Vec<String> = reader
+                .by_ref()
+                .lines()
+                .map(|l| l.unwrap())
+                .take_while(|l| !l.is_empty())
+                .collect();
+            let length = request
+                .iter()
+                .find_map(|h| h.strip_prefix("Content-Length: "))
+                .map_or(0, |n| n.parse().unwrap());
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request.push(String::from_utf8(content).unwrap());
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn input() {
+        let (url, handle) = stub_server("1\n2\n3\n");
+        let client = Client::new(&url, "abc\n").with_min_interval(Duration::ZERO);
+        assert_eq!(client.input(2022, 1).unwrap(), "1\n2\n3\n");
+
+        let request = handle.join().unwrap();
+        assert_eq!(request[0], "GET /2022/day/1/input HTTP/1.1");
+        assert!(request.iter().any(|h| h == "Cookie: session=abc"));
+    }
+
+    #[test]
+    fn submit() {
+        let (url, handle) = stub_server(
+            "<main><article><p>That's the right answer!  You are one gold star closer.</p></article></main>",
+        );
+        let client = Client::new(&url, "abc").with_min_interval(Duration::ZERO);
+        assert_eq!(client.submit(2022, 1, 2, "42").unwrap(), Verdict::Right);
+
+        let request = handle.join().unwrap();
+        assert_eq!(request[0], "POST /2022/day/1/answer HTTP/1.1");
+        assert_eq!(request.last().unwrap(), "level=2&answer=42");
+    }
+
+    #[test]
+    fn verdicts() {
+        let wrong = "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2022/about\">about page</a>.</p></article>";
+        let high = "<article><p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p></article>";
+        let low = "<article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article>";
+        let limited = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>";
+        let level = "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>";
+
+        assert_eq!(Verdict::from_response(wrong), Some(Verdict::Wrong));
+        assert_eq!(Verdict::from_response(high), Some(Verdict::TooHigh));
+        assert_eq!(Verdict::from_response(low), Some(Verdict::TooLow));
+        assert_eq!(
+            Verdict::from_response(limited),
+            Some(Verdict::RateLimited(Some(Duration::from_secs(65))))
+        );
+        assert_eq!(Verdict::from_response(level), Some(Verdict::WrongLevel));
+        assert_eq!(Verdict::from_response("<article></article>"), None);
+    }
+
+    #[test]
+    fn odd_waits() {
+        assert_eq!(
+            wait("You have 40s left to wait"),
+            Some(Duration::from_secs(40))
+        );
+        assert_eq!(wait("You have 1é left to wait"), None);
+        assert_eq!(wait("You have m left to wait"), None);
+        assert_eq!(wait("You have 5h left to wait"), None);
+    }
+}
@@ -1,16 +1,38 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
+/// any error, as the source of a located parse error
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
 #[derive(Debug, Error)]
-pub enum Error{ 
+pub enum Error {
     #[error("io error {0}")]
     IoError(#[from] std::io::Error),
-    #[error("parse error {0}")]
+    #[error("parse error: {0}")]
     ParseError(#[from] ParseError),
+    #[error("png error: {0}")]
+    PngError(#[from] png::EncodingError),
+    #[error("gif error: {0}")]
+    GifError(#[from] gif::EncodingError),
+    #[error("{0}")]
+    Other(#[from] anyhow::Error),
 }
 
+impl Error {
+    /// records the file a located parse error came from
+    pub fn with_path(self, path: impl AsRef<Path>) -> Self {
+        match self {
+            Error::ParseError(e) => Error::ParseError(e.with_path(path)),
+            e => e,
+        }
+    }
+}
 
 #[derive(Debug, Error)]
-pub enum ParseError { // TODO: unable to do genercs
+pub enum ParseError {
     #[error("invalid sequence: {0}")]
     InvalidSequence(&'static str),
     #[error("expected a digit found")]
@@ -21,4 +43,134 @@ pub enum ParseError { // TODO: unable to do genercs
     ParseIntError(#[from] std::num::ParseIntError),
     #[error("unable to parse from Str")]
     FromStrError,
-}
\ No newline at end of file
+    #[error("unexpected character {0:?}")]
+    UnexpectedChar(char),
+    #[error("expected {expected:?} but found {found:?}")]
+    Expected { expected: char, found: char },
+    #[error("unexpected token {0:?}")]
+    UnexpectedToken(String),
+    #[error("unexpected key {0:?}")]
+    UnexpectedKey(String),
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    #[error("letter {0} isn't in the font")]
+    UnknownLetter(usize),
+    #[error("no font is {0} pixels tall")]
+    UnknownFont(usize),
+    #[error("row is {found} wide but the grid is {expected} wide")]
+    RaggedRow { expected: usize, found: usize },
+    #[error("{}", .location.render(.source))]
+    Located {
+        location: Location,
+        source: BoxError,
+    },
+    #[error("unable to parse chunk {index}: {source}")]
+    Chunk {
+        index: usize,
+        source: Box<ParseError>,
+    },
+}
+
+impl ParseError {
+    /// a failure in `snippet`, the text of 1-based `line`, spanning `len` chars from 1-based `col`
+    pub fn located(
+        line: usize,
+        col: usize,
+        len: usize,
+        snippet: &str,
+        source: impl Into<BoxError>,
+    ) -> Self {
+        ParseError::Located {
+            location: Location {
+                path: None,
+                line,
+                col,
+                len,
+                snippet: snippet.to_owned(),
+            },
+            source: source.into(),
+        }
+    }
+
+    /// records the file a located error came from
+    pub fn with_path(self, path: impl AsRef<Path>) -> Self {
+        match self {
+            ParseError::Located {
+                mut location,
+                source,
+            } => {
+                location.path = Some(path.as_ref().to_path_buf());
+                ParseError::Located { location, source }
+            }
+            ParseError::Chunk { index, source } => ParseError::Chunk {
+                index,
+                source: Box::new(source.with_path(path)),
+            },
+            e => e,
+        }
+    }
+}
+
+/// where in an input a parse failed
+#[derive(Debug, Clone)]
+pub struct Location {
+    pub path: Option<PathBuf>,
+    pub line: usize,
+    pub col: usize,
+    pub len: usize,
+    pub snippet: String,
+}
+
+impl Location {
+    /// renders `message` like a compiler diagnostic, e.g.
+    /// ```text
+    /// invalid digit found in string
+    ///  --> ./inputs/aoc2021/inputs/day01.txt:3:1
+    ///   |
+    /// 3 | 12a
+    ///   | ^^^
+    /// ```
+    pub fn render(&self, message: &impl Display) -> String {
+        let path = self
+            .path
+            .as_ref()
+            .map_or("<input>".to_owned(), |p| p.display().to_string());
+        let gutter = " ".repeat(self.line.to_string().len());
+        format!(
+            "{message}\n{gutter}--> {path}:{line}:{col}\n{gutter} |\n{line} | {snippet}\n{gutter} | {pad}{carets}",
+            message = message,
+            gutter = gutter,
+            path = path,
+            line = self.line,
+            col = self.col,
+            snippet = self.snippet,
+            pad = " ".repeat(self.col.saturating_sub(1)),
+            carets = "^".repeat(self.len.max(1)),
+        )
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("no session token. set AOC_SESSION or write it to {0}")]
+    MissingSession(String),
+    #[error("request failed: {0}")]
+    Http(#[from] Box<ureq::Error>),
+    #[error("io error {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("unexpected response: {0}")]
+    UnexpectedResponse(String),
+}
+
+/// reasons the submission history refuses an answer before it is sent
+#[derive(Debug, Error)]
+pub enum SubmitError {
+    #[error("{0} was already submitted and was wrong")]
+    KnownWrong(String),
+    #[error("{answer} is not below {bound}, which is known to be too high")]
+    TooHigh { answer: String, bound: i64 },
+    #[error("{answer} is not above {bound}, which is known to be too low")]
+    TooLow { answer: String, bound: i64 },
+    #[error("already solved with {0}")]
+    AlreadySolved(String),
+}
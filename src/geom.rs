@@ -0,0 +1,344 @@
+//! points and vectors on an integer lattice
+
+use crate::error::ParseError;
+use std::{
+    fmt::Display,
+    num::ParseIntError,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// the signed integers a point can be made of
+pub trait Coord:
+    Copy
+    + Ord
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + FromStr<Err = ParseIntError>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const NEG_ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const NEG_ONE: Self = -1;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )*};
+}
+
+coord!(i8, i16, i32, i64, i128, isize);
+
+/// y grows downwards, like the rows of a puzzle grid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Coord> Point2<T> {
+    pub const ORIGIN: Self = Point2::new(T::ZERO, T::ZERO);
+    pub const UP: Self = Point2::new(T::ZERO, T::NEG_ONE);
+    pub const DOWN: Self = Point2::new(T::ZERO, T::ONE);
+    pub const LEFT: Self = Point2::new(T::NEG_ONE, T::ZERO);
+    pub const RIGHT: Self = Point2::new(T::ONE, T::ZERO);
+    /// up, right, down, left
+    pub const ORTHOGONAL: [Self; 4] = [Self::UP, Self::RIGHT, Self::DOWN, Self::LEFT];
+    /// clockwise from up
+    pub const ADJACENT: [Self; 8] = [
+        Self::UP,
+        Point2::new(T::ONE, T::NEG_ONE),
+        Self::RIGHT,
+        Point2::new(T::ONE, T::ONE),
+        Self::DOWN,
+        Point2::new(T::NEG_ONE, T::ONE),
+        Self::LEFT,
+        Point2::new(T::NEG_ONE, T::NEG_ONE),
+    ];
+
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// the number of king moves between the points
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// each component reduced to -1, 0 or 1. a single step towards where `self` points
+    pub fn signum(self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Self::ORTHOGONAL.into_iter().map(move |d| self + d)
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Self::ADJACENT.into_iter().map(move |d| self + d)
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    pub const ORIGIN: Self = Point3::new(T::ZERO, T::ZERO, T::ZERO);
+
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    pub fn signum(self) -> Self {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// `m` times the point as a column vector
+    pub fn rotate(self, m: &Matrix3<T>) -> Self {
+        let [x, y, z] = m.map(|[a, b, c]| a * self.x + b * self.y + c * self.z);
+        Point3::new(x, y, z)
+    }
+}
+
+/// row major
+pub type Matrix3<T> = [[T; 3]; 3];
+
+/// the 24 ways to turn a cube, as the signed permutation matrices with a determinant of 1.
+/// the identity comes first
+pub fn rotations<T: Coord>() -> Vec<Matrix3<T>> {
+    const PERMUTATIONS: [([usize; 3], bool); 6] = [
+        ([0, 1, 2], true),
+        ([0, 2, 1], false),
+        ([1, 0, 2], false),
+        ([1, 2, 0], true),
+        ([2, 0, 1], true),
+        ([2, 1, 0], false),
+    ];
+    let mut matrices = Vec::with_capacity(24);
+    for (axes, even) in PERMUTATIONS {
+        for flips in 0..8u8 {
+            // an odd permutation needs an odd number of flips to stay right handed
+            if (flips.count_ones() % 2 == 0) != even {
+                continue;
+            }
+            let mut m = [[T::ZERO; 3]; 3];
+            for (row, &axis) in axes.iter().enumerate() {
+                m[row][axis] = if flips & (1 << row) == 0 {
+                    T::ONE
+                } else {
+                    T::NEG_ONE
+                };
+            }
+            matrices.push(m);
+        }
+    }
+    matrices
+}
+
+macro_rules! ops {
+    ($point:ident { $($f:ident),* }) => {
+        impl<T: Coord> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $point { $($f: self.$f + rhs.$f),* }
+            }
+        }
+
+        impl<T: Coord> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $point { $($f: self.$f - rhs.$f),* }
+            }
+        }
+
+        impl<T: Coord> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($f: -self.$f),* }
+            }
+        }
+
+        /// scaling by a scalar
+        impl<T: Coord> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $point { $($f: self.$f * rhs),* }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Coord> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        /// `x,y` or `x=..,y=..`, with or without spaces after the commas
+        impl<T: Coord> FromStr for $point<T> {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut parts = s.trim().split(',');
+                $(
+                    let part = parts.next().ok_or(ParseError::UnexpectedEnd)?.trim();
+                    let part = part
+                        .strip_prefix(concat!(stringify!($f), "="))
+                        .unwrap_or(part);
+                    let $f = part.parse()?;
+                )*
+                match parts.next() {
+                    None => Ok($point { $($f),* }),
+                    Some(rest) => Err(ParseError::UnexpectedToken(rest.to_owned())),
+                }
+            }
+        }
+    };
+}
+
+ops!(Point2 { x, y });
+ops!(Point3 { x, y, z });
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(3, 4);
+        assert_eq!(a + b, Point2::new(4, 2));
+        assert_eq!(a - b, Point2::new(-2, -6));
+        assert_eq!(-a, Point2::new(-1, 2));
+        assert_eq!(b * 2, Point2::new(6, 8));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(1, 1, 1),
+            Point3::new(2, 3, 4)
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(8, 7);
+        let b = Point2::new(2, 10);
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!((b - a).signum(), Point2::new(-1, 1));
+        let p = Point3::new(1105, -1205, 1229);
+        let q = Point3::new(-92, -2380, -20);
+        assert_eq!(p.manhattan(q), 3621);
+        assert_eq!(p.chebyshev(q), 1249);
+    }
+
+    #[test]
+    fn directions() {
+        let p = Point2::new(0, 0);
+        assert_eq!(p.neighbours4().collect::<HashSet<_>>().len(), 4);
+        assert!(p.neighbours8().all(|n| n.chebyshev(p) == 1));
+        assert_eq!(Point2::UP + Point2::DOWN, Point2::<i32>::ORIGIN);
+    }
+
+    #[test]
+    fn parse() {
+        let p = Point2::new(-2, 15);
+        assert_eq!("-2,15".parse::<Point2<i32>>().unwrap(), p);
+        assert_eq!("x=-2, y=15".parse::<Point2<i32>>().unwrap(), p);
+        assert_eq!(" -2, 15\n".parse::<Point2<i32>>().unwrap(), p);
+        let q = Point3::new(1, 2, 3);
+        assert_eq!("x=1,y=2,z=3".parse::<Point3<i32>>().unwrap(), q);
+        assert!(matches!(
+            "1,2,3".parse::<Point2<i32>>(),
+            Err(ParseError::UnexpectedToken(_))
+        ));
+        assert!(matches!(
+            "1".parse::<Point2<i32>>(),
+            Err(ParseError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            "x=1,y=b".parse::<Point2<i32>>(),
+            Err(ParseError::ParseIntError(_))
+        ));
+        assert_eq!(q.to_string().parse::<Point3<i32>>().unwrap(), q);
+    }
+
+    #[test]
+    fn rotation() {
+        let rotations = rotations::<i32>();
+        assert_eq!(rotations.len(), 24);
+        let p = Point3::new(2, 3, 5);
+        assert_eq!(p.rotate(&rotations[0]), p);
+        let rotated: HashSet<_> = rotations.iter().map(|r| p.rotate(r)).collect();
+        assert_eq!(rotated.len(), 24);
+        // a quarter turn about z, applied four times, is the identity
+        let quarter = [[0, -1, 0], [1, 0, 0], [0, 0, 1]];
+        assert!(rotations.contains(&quarter));
+        let turned = (0..4).fold(p, |p, _| p.rotate(&quarter));
+        assert_eq!(turned, p);
+    }
+}
@@ -0,0 +1,384 @@
+//! a dense 2d grid, the shape most puzzle inputs come in
+
+use crate::error::{Error, ParseError};
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// `(x, y)` with x the column and y the row, counted from the top left
+pub type Pos = (usize, usize);
+
+/// steps to the orthogonal neighbours: up, right, down, left
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// steps to every neighbour, clockwise from up
+pub const ADJACENT: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// cells are stored row by row in a single buffer
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// a `width` x `height` grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// rows become columns
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// a quarter turn clockwise
+    pub fn rotate_cw(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// a quarter turn anticlockwise
+    pub fn rotate_ccw(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    /// builds each cell from its position
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// one row per line, mapping each char with `f`. e.g. a grid of digits.
+    /// the grid has at least one cell
+    pub fn parse_with(data: &str, f: impl Fn(char) -> Option<T>) -> Result<Self, Error> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(data.len());
+        for (i, line) in data.lines().enumerate() {
+            let found = line.chars().count();
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                let e = ParseError::RaggedRow { expected, found };
+                return Err(ParseError::located(i + 1, 1, found, line, e).into());
+            }
+            for (j, c) in line.chars().enumerate() {
+                let cell = f(c).ok_or_else(|| {
+                    ParseError::located(i + 1, j + 1, 1, line, ParseError::UnexpectedChar(c))
+                })?;
+                cells.push(cell);
+            }
+            height += 1;
+        }
+        if cells.is_empty() {
+            return Err(ParseError::UnexpectedEnd.into());
+        }
+        Ok(Grid {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        self.index_of((x, y)).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        self.index_of((x, y)).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, (x, y): Pos) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// `pos` moved by `step`, if that is still on the grid
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// the up to 4 orthogonal neighbours of `pos` that are on the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// the up to 8 neighbours of `pos`, diagonals included, that are on the grid
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ADJACENT.into_iter().filter_map(move |d| self.step(pos, d))
+    }
+
+    /// every position from `pos`, exclusive, stepping by `step` until the edge
+    pub fn ray(&self, pos: Pos, step: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, step), move |&p| self.step(p, step))
+    }
+
+    /// every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// every cell, row by row
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// panics if `x` is off the grid, as indexing does
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is off the grid", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.col(x))
+    }
+
+    /// the first position, row by row, holding `value`
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, c)| *c == value).map(|(p, _)| p)
+    }
+
+    /// every position holding `value`
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, c)| *c == value)
+            .map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "{:?} is off the grid",
+            (x, y)
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "{:?} is off the grid",
+            (x, y)
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// panics if the rows aren't all the same width
+impl<T> From<Vec<Vec<T>>> for Grid<T> {
+    fn from(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|r| r.len() == width), "ragged rows");
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+}
+
+/// a character grid, e.g. `Grid<char>` or `Grid<u8>` for ascii
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, |c| T::try_from(c).ok())
+    }
+}
+
+/// one line per row
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], 'c');
+        assert_eq!(grid[(0, 1)], 'd');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits = Grid::parse_with("12\n34\r\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits.values().sum::<u32>(), 10);
+        assert!(Grid::parse_with("12\n3a", |c| c.to_digit(10)).is_err());
+    }
+
+    #[test]
+    fn empty() {
+        for data in ["", "\n\n"] {
+            assert!(matches!(
+                data.parse::<Grid<char>>(),
+                Err(Error::ParseError(ParseError::UnexpectedEnd))
+            ));
+        }
+    }
+
+    #[test]
+    fn ragged() {
+        let err = "abc\nde".parse::<Grid<char>>().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("row is 2 wide but the grid is 3 wide"));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(13, 11, 0);
+        let sorted = |it: &mut dyn Iterator<Item = Pos>| {
+            let mut v: Vec<_> = it.collect();
+            v.sort();
+            v
+        };
+        assert_eq!(sorted(&mut grid.neighbours4((0, 0))), vec![(0, 1), (1, 0)]);
+        assert_eq!(
+            sorted(&mut grid.neighbours4((1, 1))),
+            vec![(0, 1), (1, 0), (1, 2), (2, 1)]
+        );
+        assert_eq!(
+            sorted(&mut grid.neighbours4((12, 10))),
+            vec![(11, 10), (12, 9)]
+        );
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((5, 5)).count(), 8);
+        assert_eq!(grid.neighbours8((12, 5)).count(), 5);
+    }
+
+    #[test]
+    fn lines() {
+        let grid = sample();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.col(1).collect::<String>(), "be");
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.cols().count(), 3);
+        let ray: Vec<_> = grid.ray((0, 0), (1, 0)).collect();
+        assert_eq!(ray, vec![(1, 0), (2, 0)]);
+        assert_eq!(grid.ray((2, 1), (-1, -1)).collect::<Vec<_>>(), vec![(1, 0)]);
+        assert_eq!(grid.ray((0, 0), (0, -1)).count(), 0);
+        let empty = Grid::new(0, 3, 'a');
+        assert_eq!(empty.cols().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "off the grid")]
+    fn col_off_grid() {
+        let _ = sample().col(3);
+    }
+
+    #[test]
+    fn find_and_map() {
+        let grid = sample();
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+        assert_eq!(grid.find(&'z'), None);
+        let vowels = grid.map(|c| "aeiou".contains(*c));
+        assert_eq!(
+            vowels.find_all(&true).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn transform() {
+        let grid = sample();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}
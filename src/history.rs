@@ -0,0 +1,194 @@
+//! a local record of submitted answers, so known bad answers are never sent twice
+use crate::{
+    client::Verdict,
+    error::{Error, ParseError, SubmitError},
+};
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+pub const DEFAULT_PATH: &str = "./inputs/submissions.txt";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub year: usize,
+    pub day: usize,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// one tab separated attempt per line: year, day, part, verdict, answer
+impl Display for Attempt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.year, self.day, self.part, self.verdict, self.answer
+        )
+    }
+}
+
+impl FromStr for Attempt {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.splitn(5, '\t');
+        let mut next = || fields.next().ok_or(ParseError::FromStrError);
+        Ok(Attempt {
+            year: next()?.parse()?,
+            day: next()?.parse()?,
+            part: next()?.parse()?,
+            verdict: next()?.parse()?,
+            answer: next()?.to_owned(),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// reads the history at `path`. a missing file is an empty history
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let attempts = match fs::read_to_string(&path) {
+            Ok(data) => data
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, attempts })
+    }
+
+    /// refuses answers that are already known to be wrong, or fall outside
+    /// the bounds set by earlier too high and too low verdicts
+    pub fn check(
+        &self,
+        year: usize,
+        day: usize,
+        part: u8,
+        answer: &str,
+    ) -> Result<(), SubmitError> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|a| a.year == year && a.day == day && a.part == part);
+        let value = answer.parse::<i64>().ok();
+        for attempt in attempts {
+            let bound = attempt.answer.parse::<i64>().ok();
+            match (attempt.verdict, value.zip(bound)) {
+                (Verdict::Right, _) => {
+                    return Err(SubmitError::AlreadySolved(attempt.answer.clone()))
+                }
+                (Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow, _)
+                    if attempt.answer == answer =>
+                {
+                    return Err(SubmitError::KnownWrong(answer.to_owned()))
+                }
+                (Verdict::TooHigh, Some((value, bound))) if value >= bound => {
+                    return Err(SubmitError::TooHigh {
+                        answer: answer.to_owned(),
+                        bound,
+                    })
+                }
+                (Verdict::TooLow, Some((value, bound))) if value <= bound => {
+                    return Err(SubmitError::TooLow {
+                        answer: answer.to_owned(),
+                        bound,
+                    })
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// appends an attempt to the history file
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), Error> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", attempt)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn attempt(answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            year: 2022,
+            day: 1,
+            part: 1,
+            answer: answer.to_owned(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let a = attempt("1 2\t3", Verdict::TooLow);
+        assert_eq!(a.to_string().parse::<Attempt>().unwrap(), a);
+    }
+
+    #[test]
+    fn check() {
+        let history = History {
+            path: PathBuf::new(),
+            attempts: vec![
+                attempt("100", Verdict::TooHigh),
+                attempt("10", Verdict::TooLow),
+                attempt("50", Verdict::Wrong),
+                attempt("60", Verdict::RateLimited(None)),
+            ],
+        };
+        assert!(matches!(
+            history.check(2022, 1, 1, "50"),
+            Err(SubmitError::KnownWrong(_))
+        ));
+        assert!(matches!(
+            history.check(2022, 1, 1, "100"),
+            Err(SubmitError::KnownWrong(_))
+        ));
+        assert!(matches!(
+            history.check(2022, 1, 1, "150"),
+            Err(SubmitError::TooHigh { bound: 100, .. })
+        ));
+        assert!(matches!(
+            history.check(2022, 1, 1, "5"),
+            Err(SubmitError::TooLow { bound: 10, .. })
+        ));
+        assert!(history.check(2022, 1, 1, "60").is_ok());
+        assert!(history.check(2022, 1, 2, "150").is_ok());
+    }
+
+    #[test]
+    fn solved() {
+        let history = History {
+            path: PathBuf::new(),
+            attempts: vec![attempt("42", Verdict::Right)],
+        };
+        assert!(matches!(
+            history.check(2022, 1, 1, "43"),
+            Err(SubmitError::AlreadySolved(_))
+        ));
+    }
+}
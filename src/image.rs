@@ -0,0 +1,263 @@
+//! saving grids as png pictures and gif animations, a square of pixels per cell
+
+use crate::{aoc2021, aoc2022, downcast, error::Error, grid::Grid, DynSolution, Solution};
+use std::{any::Any, collections::HashMap, io::Write, time::Duration};
+
+/// red, green and blue
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const RED: Rgb = [220, 50, 47];
+
+/// colours far enough apart to tell neighbouring regions apart
+pub const DISTINCT: [Rgb; 8] = [
+    [230, 159, 0],
+    [86, 180, 233],
+    [0, 158, 115],
+    [240, 228, 66],
+    [0, 114, 178],
+    [213, 94, 0],
+    [204, 121, 167],
+    [153, 153, 153],
+];
+
+/// the colour `t` of the way from `from` to `to`, with `t` clamped to between 0 and 1
+pub fn gradient(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    [0, 1, 2].map(|i| (from[i] as f64 + (to[i] as f64 - from[i] as f64) * t).round() as u8)
+}
+
+/// every cell as a `scale` x `scale` square, row by row
+fn pixels(image: &Grid<Rgb>, scale: usize) -> Vec<u8> {
+    image
+        .rows()
+        .flat_map(|row| {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|rgb| std::iter::repeat_n(rgb, scale).flatten().copied())
+                .collect();
+            std::iter::repeat_n(line, scale).flatten()
+        })
+        .collect()
+}
+
+/// writes `image` as a png, each cell `scale` pixels square
+pub fn write_png(out: impl Write, image: &Grid<Rgb>, scale: usize) -> Result<(), Error> {
+    let (width, height) = (image.width() * scale, image.height() * scale);
+    let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels(image, scale))?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// an animation written a frame at a time, so a long simulation needn't all be held at once
+pub struct Gif<W: Write> {
+    encoder: gif::Encoder<W>,
+    width: usize,
+    height: usize,
+    scale: usize,
+    /// in hundredths of a second, as gif counts them
+    delay: u16,
+}
+
+impl<W: Write> Gif<W> {
+    /// every frame must be `width` x `height` cells, and is shown for `delay` before the next.
+    /// the animation loops forever
+    pub fn new(
+        out: W,
+        width: usize,
+        height: usize,
+        scale: usize,
+        delay: Duration,
+    ) -> Result<Self, Error> {
+        let size = |cells: usize| {
+            u16::try_from(cells * scale)
+                .map_err(|_| anyhow::anyhow!("{} pixels is too large for a gif", cells * scale))
+        };
+        let mut encoder = gif::Encoder::new(out, size(width)?, size(height)?, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        Ok(Gif {
+            encoder,
+            width,
+            height,
+            scale,
+            delay: (delay.as_millis() / 10).min(u16::MAX as u128) as u16,
+        })
+    }
+
+    /// appends a frame, which must be the size given to `new`.
+    /// one with more than 256 colours is quantized, which is slow
+    pub fn push(&mut self, image: &Grid<Rgb>) -> Result<(), Error> {
+        if (image.width(), image.height()) != (self.width, self.height) {
+            return Err(anyhow::anyhow!(
+                "a {}x{} frame doesn't fit a {}x{} gif",
+                image.width(),
+                image.height(),
+                self.width,
+                self.height
+            )
+            .into());
+        }
+        let (width, height) = (
+            (self.width * self.scale) as u16,
+            (self.height * self.scale) as u16,
+        );
+        let pixels = pixels(image, self.scale);
+
+        // the colours in the order they're first seen, stopping once there are too many
+        let mut palette: HashMap<Rgb, usize> = HashMap::new();
+        for rgb in image.values() {
+            let next = palette.len();
+            palette.entry(*rgb).or_insert(next);
+            if palette.len() > 256 {
+                break;
+            }
+        }
+        let mut frame = if palette.len() <= 256 {
+            let mut colors = vec![0; palette.len() * 3];
+            for (rgb, &i) in palette.iter() {
+                colors[i * 3..i * 3 + 3].copy_from_slice(rgb);
+            }
+            let indexed: Vec<u8> = pixels
+                .chunks(3)
+                .map(|rgb| palette[&[rgb[0], rgb[1], rgb[2]]] as u8)
+                .collect();
+            gif::Frame::from_palette_pixels(width, height, &indexed, &colors, None)
+        } else {
+            gif::Frame::from_rgb_speed(width, height, &pixels, 10)
+        };
+        frame.delay = self.delay;
+        self.encoder.write_frame(&frame)?;
+        Ok(())
+    }
+}
+
+/// a solution whose state can be saved as pictures
+pub trait Export: Solution {
+    /// solves `parsed` again, calling `emit` with a picture of each step along the way.
+    /// a day with one picture to show, e.g. a map, emits it once
+    fn export(parsed: &Self::Parsed, emit: &mut dyn FnMut(Grid<Rgb>));
+}
+
+/// object safe view of an [`Export`], as [`DynSolution`] is of a [`Solution`]
+pub trait DynExport: DynSolution {
+    /// panics if `parsed` didn't come from this solution's `parse_input`
+    fn export(&self, parsed: &dyn Any, emit: &mut dyn FnMut(Grid<Rgb>));
+}
+
+impl<S: Export> DynExport for S {
+    fn export(&self, parsed: &dyn Any, emit: &mut dyn FnMut(Grid<Rgb>)) {
+        S::export(downcast::<S>(parsed), emit)
+    }
+}
+
+/// every day that can be exported, ordered by year then day
+pub fn registry() -> Vec<Box<dyn DynExport>> {
+    vec![
+        Box::new(aoc2021::day09::Day09),
+        Box::new(aoc2021::day15::Day15),
+        Box::new(aoc2022::day12::Day12),
+        Box::new(aoc2022::day14::Day14),
+    ]
+}
+
+/// look up a single day's export
+pub fn find(year: usize, day: usize) -> Option<Box<dyn DynExport>> {
+    registry().into_iter().find(|e| {
+        let problem = e.problem();
+        problem.year == year && problem.day == day
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    fn sample() -> Grid<Rgb> {
+        Grid::from(vec![vec![RED, WHITE], vec![BLACK, RED]])
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(gradient(BLACK, WHITE, 0.5), [128, 128, 128]);
+        assert_eq!(gradient(RED, WHITE, -1.0), RED);
+        assert_eq!(gradient(RED, WHITE, 2.0), WHITE);
+    }
+
+    #[test]
+    fn png() {
+        let mut out = Vec::new();
+        write_png(&mut out, &sample(), 3).unwrap();
+
+        let mut reader = png::Decoder::new(out.as_slice()).read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (6, 6));
+        let at = |x: usize, y: usize| &buf[(y * 6 + x) * 3..(y * 6 + x) * 3 + 3];
+        assert_eq!(at(2, 2), RED);
+        assert_eq!(at(3, 2), WHITE);
+        assert_eq!(at(0, 3), BLACK);
+        assert_eq!(at(5, 5), RED);
+    }
+
+    #[test]
+    fn gif() {
+        let mut out = Vec::new();
+        let mut gif = Gif::new(&mut out, 2, 2, 2, Duration::from_millis(100)).unwrap();
+        gif.push(&sample()).unwrap();
+        gif.push(&sample().rotate_cw()).unwrap();
+        gif.push(&Grid::from_fn(2, 2, |_| WHITE)).unwrap();
+        assert!(gif.push(&Grid::new(3, 2, WHITE)).is_err());
+        drop(gif);
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(out.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (4, 4));
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            frames.push(frame.buffer.to_vec());
+        }
+        assert_eq!(frames.len(), 3);
+        let at =
+            |frame: &[u8], x: usize, y: usize| frame[(y * 4 + x) * 4..(y * 4 + x) * 4 + 3].to_vec();
+        assert_eq!(at(&frames[0], 0, 0), RED);
+        assert_eq!(at(&frames[0], 2, 0), WHITE);
+        assert_eq!(at(&frames[1], 0, 0), BLACK);
+        assert_eq!(at(&frames[2], 3, 3), WHITE);
+    }
+
+    #[test]
+    fn gif_quantized() {
+        // too many colours for one palette
+        let image = Grid::from_fn(20, 20, |(x, y)| [x as u8 * 12, y as u8 * 12, 128]);
+        let mut out = Vec::new();
+        let mut gif = Gif::new(&mut out, 20, 20, 1, Duration::ZERO).unwrap();
+        gif.push(&image).unwrap();
+        drop(gif);
+        let mut decoder = gif::DecodeOptions::new().read_info(out.as_slice()).unwrap();
+        assert!(decoder.read_next_frame().unwrap().is_some());
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+
+    /// every export runs on its first example, and its pictures keep one size
+    #[test]
+    fn examples() {
+        for export in registry() {
+            let problem = export.problem();
+            let data = read_to_string(problem.example_path("_1")).unwrap();
+            let parsed = export.parse_input(&data).unwrap();
+            let mut images = Vec::new();
+            export.export(parsed.as_ref(), &mut |image| images.push(image));
+            assert!(!images.is_empty(), "{} day {}", problem.year, problem.day);
+            let size = |i: &Grid<Rgb>| (i.width(), i.height());
+            assert!(images.iter().all(|i| size(i) == size(&images[0])));
+        }
+    }
+}
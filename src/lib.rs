@@ -1,11 +1,32 @@
 pub mod aoc2021;
 pub mod aoc2022;
+pub mod answers;
+pub mod bits;
+pub mod client;
 pub mod error;
+pub mod geom;
+pub mod grid;
+pub mod history;
+pub mod image;
+pub mod math;
+pub mod ocr;
+pub mod puzzle;
+pub mod range_set;
+pub mod search;
+pub mod simulate;
+pub mod visualize;
 
 use crate::error::Error;
-use error::ParseError;
-use std::{fs::read_to_string, path::Path, str::FromStr};
+use error::{BoxError, ParseError};
+use std::{
+    any::Any,
+    fmt::Display,
+    fs::{self, read_to_string},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
+#[derive(Debug, Clone, Copy)]
 pub struct Problem {
     pub year: usize,
     pub day: usize,
@@ -21,23 +42,135 @@ impl Problem {
         format!("./inputs/aoc{}/examples/day{:02}{}.txt", self.year, self.day, id)
     }
 
+    /// a file the tests use that isn't an example, e.g. a malformed input.
+    /// kept out of `examples` so it's never checked against examples.toml
+    pub fn fixture_path(&self, id: &str) -> impl AsRef<Path> {
+        format!("./inputs/aoc{}/fixtures/day{:02}{}.txt", self.year, self.day, id)
+    }
+
+    /// every input on disk, sorted by id. `dayNN.txt` is the `default` input
+    /// and `dayNN_<id>.txt` beside it is input `<id>`
+    pub fn inputs(&self) -> Vec<(String, PathBuf)> {
+        let mut inputs: Vec<(String, PathBuf)> = self
+            .files("inputs")
+            .into_iter()
+            .map(|(suffix, path)| match suffix.strip_prefix('_') {
+                Some(id) => (id.to_owned(), path),
+                None => (answers::DEFAULT_INPUT.to_owned(), path),
+            })
+            .collect();
+        inputs.sort();
+        inputs
+    }
+
+    /// every example on disk, sorted by id. the ids are the suffixes that
+    /// `example_path` takes, e.g. `_1` or `_small`
+    pub fn examples(&self) -> Vec<(String, PathBuf)> {
+        self.files("examples")
+    }
+
+    /// the `dayNN.txt` and `dayNN_*.txt` files in `inputs/aocYYYY/<kind>`, by suffix
+    fn files(&self, kind: &str) -> Vec<(String, PathBuf)> {
+        let dir = format!("./inputs/aoc{}/{}", self.year, kind);
+        let prefix = format!("day{:02}", self.day);
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut files: Vec<(String, PathBuf)> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let name = path.file_name()?.to_str()?;
+                let suffix = name.strip_prefix(&prefix)?.strip_suffix(".txt")?.to_owned();
+                (suffix.is_empty() || suffix.starts_with('_')).then_some((suffix, path))
+            })
+            .collect();
+        files.sort();
+        files
+    }
 }
+
+/// every solved problem, ordered by year then day
+pub fn registry() -> Vec<Box<dyn DynSolution>> {
+    let mut solutions = Vec::new();
+    solutions.extend(aoc2021::solutions());
+    solutions.extend(aoc2022::solutions());
+    solutions
+}
+
+/// look up a single problem in the registry
+pub fn find(year: usize, day: usize) -> Option<Box<dyn DynSolution>> {
+    registry().into_iter().find(|s| {
+        let problem = s.problem();
+        problem.year == year && problem.day == day
+    })
+}
+
+/// both parts share the result of a single `parse`
 pub trait Solution {
-    type OutputPt1;
-    type OutputPt2;
-    fn pt1(data: &str) -> Self::OutputPt1;
-    fn pt2(data: &str) -> Self::OutputPt2;
+    const PROBLEM: Problem;
+    type Parsed: 'static;
+    type OutputPt1: Display;
+    type OutputPt2: Display;
+    fn parse(data: &str) -> Result<Self::Parsed, Error>;
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1;
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2;
+}
+
+/// object safe view of a [`Solution`] so different days can live in one collection.
+/// the parsed input is type erased and answers are rendered with their `Display` impl
+pub trait DynSolution {
+    fn problem(&self) -> Problem;
+    fn parse_input(&self, data: &str) -> Result<Box<dyn Any>, Error>;
+    /// panics if `parsed` didn't come from this solution's `parse_input`
+    fn run_pt1(&self, parsed: &dyn Any) -> String;
+    /// panics if `parsed` didn't come from this solution's `parse_input`
+    fn run_pt2(&self, parsed: &dyn Any) -> String;
+}
+
+impl<S: Solution> DynSolution for S {
+    fn problem(&self) -> Problem {
+        S::PROBLEM
+    }
+
+    fn parse_input(&self, data: &str) -> Result<Box<dyn Any>, Error> {
+        Ok(Box::new(S::parse(data)?))
+    }
+
+    fn run_pt1(&self, parsed: &dyn Any) -> String {
+        S::pt1(downcast::<S>(parsed)).to_string()
+    }
+
+    fn run_pt2(&self, parsed: &dyn Any) -> String {
+        S::pt2(downcast::<S>(parsed)).to_string()
+    }
+}
+
+pub(crate) fn downcast<S: Solution>(parsed: &dyn Any) -> &S::Parsed {
+    parsed
+        .downcast_ref()
+        .expect("parsed input should come from the same solution")
 }
 
 pub fn read_to_one_per_line<T>(path: impl AsRef<Path>) -> Result<Vec<T>, Error>
 where
     T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    parse_one_per_line(&read_to_string(&path)?).map_err(|e| e.with_path(path))
+}
+
+/// parses each line with `T::from_str`. a failure points at the whole line
+pub fn parse_one_per_line<T>(data: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
 {
-    read_to_string(path)?
-        .lines()
-        .map(|l| match l.parse::<T>() {
-            Ok(v) => Ok(v),
-            Err(_) => Err(Error::ParseError(ParseError::FromStrError)),
+    data.lines()
+        .enumerate()
+        .map(|(i, l)| {
+            l.parse::<T>().map_err(|e| {
+                ParseError::located(i + 1, 1, l.chars().count(), l, e.into()).into()
+            })
         })
         .collect()
 }
@@ -47,22 +180,150 @@ where
     T: FromStr,
     F: Fn(char) -> Option<T>,
 {
-    read_to_string(path)?
-        .lines()
-        .map(|line| {
+    parse_vec_per_line(&read_to_string(&path)?, f).map_err(|e| e.with_path(path))
+}
+
+/// one row per line, mapping each char with `f`. e.g. a grid of digits
+pub fn parse_vec_per_line<T, F>(data: &str, f: F) -> Result<Vec<Vec<T>>, Error>
+where
+    T: FromStr,
+    F: Fn(char) -> Option<T>,
+{
+    data.lines()
+        .enumerate()
+        .map(|(i, line)| {
             line.chars()
-                .map(|c| match f(c) {
-                    Some(v) => Ok(v),
-                    None => Err(Error::ParseError(ParseError::FromStrError)),
+                .enumerate()
+                .map(|(j, c)| {
+                    f(c).ok_or_else(|| {
+                        ParseError::located(i + 1, j + 1, 1, line, ParseError::UnexpectedChar(c))
+                            .into()
+                    })
                 })
                 .collect()
         })
         .collect()
 }
 
-pub fn read_to_chunks(path: impl AsRef<Path>) -> Result<Vec<String>, Error> {
-    read_to_string(path)?
-        .split("\r\n\r\n")
-        .map(|s| Ok(s.to_owned()))
+/// `\r\n` line endings become `\n` and the trailing newline is dropped
+pub fn normalize(data: &str) -> String {
+    data.replace("\r\n", "\n").trim_end_matches('\n').to_owned()
+}
+
+/// reads an input with normalized line endings
+pub fn read_input(path: impl AsRef<Path>) -> Result<String, Error> {
+    Ok(normalize(&read_to_string(path)?))
+}
+
+pub fn read_chunks<T>(path: impl AsRef<Path>) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    parse_chunks(&read_to_string(&path)?).map_err(|e| e.with_path(path))
+}
+
+/// parses each blank line separated chunk, whatever the line endings.
+/// a failure names the index of the chunk and points at its first line
+pub fn parse_chunks<T>(data: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    let mut line = 1;
+    normalize(data)
+        .split("\n\n")
+        .enumerate()
+        .map(|(index, chunk)| {
+            let start = line;
+            line += chunk.lines().count() + 1;
+            chunk.parse().map_err(|e: T::Err| {
+                let first = chunk.lines().next().unwrap_or_default();
+                let located = ParseError::located(start, 1, first.chars().count(), first, e);
+                Error::ParseError(ParseError::Chunk {
+                    index,
+                    source: Box::new(located),
+                })
+            })
+        })
         .collect()
 }
+
+pub fn read_to_chunks(path: impl AsRef<Path>) -> Result<Vec<String>, Error> {
+    read_chunks(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the sum of a chunk of one number per line
+    #[derive(Debug, PartialEq)]
+    struct Sum(usize);
+
+    impl FromStr for Sum {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.lines()
+                .map(str::parse::<usize>)
+                .sum::<Result<_, _>>()
+                .map(Sum)
+        }
+    }
+
+    #[test]
+    fn normalize_line_endings() {
+        assert_eq!(normalize("1\r\n2\r\n\r\n3\r\n"), "1\n2\n\n3");
+        assert_eq!(normalize("1\n2\n\n3\n"), "1\n2\n\n3");
+    }
+
+    #[test]
+    fn chunks_lf() {
+        let chunks = read_chunks::<Sum>("./inputs/fixtures/chunks_lf.txt").unwrap();
+        assert_eq!(chunks, vec![Sum(3), Sum(3), Sum(9)]);
+    }
+
+    #[test]
+    fn chunks_crlf() {
+        let chunks = read_chunks::<Sum>("./inputs/fixtures/chunks_crlf.txt").unwrap();
+        assert_eq!(chunks, vec![Sum(3), Sum(3), Sum(9)]);
+    }
+
+    #[test]
+    fn chunks_as_strings() {
+        let lf = read_to_chunks("./inputs/fixtures/chunks_lf.txt").unwrap();
+        let crlf = read_to_chunks("./inputs/fixtures/chunks_crlf.txt").unwrap();
+        assert_eq!(lf, vec!["1\n2", "3", "4\n5"]);
+        assert_eq!(lf, crlf);
+    }
+
+    #[test]
+    fn located_line_error() {
+        let err = parse_one_per_line::<usize>("1\n2\n12a\n")
+            .unwrap_err()
+            .with_path("day01.txt");
+        assert_eq!(
+            err.to_string(),
+            "parse error: invalid digit found in string\n --> day01.txt:3:1\n  |\n3 | 12a\n  | ^^^"
+        );
+    }
+
+    #[test]
+    fn located_char_error() {
+        let err = parse_vec_per_line("123\n4x6\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse error: unexpected character 'x'\n --> <input>:2:2\n  |\n2 | 4x6\n  |  ^"
+        );
+    }
+
+    #[test]
+    fn chunk_error_names_index() {
+        let err = parse_chunks::<Sum>("1\r\n\r\n2\r\nx\r\n").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ParseError(ParseError::Chunk { index: 1, .. })
+        ));
+    }
+}
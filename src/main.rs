@@ -1,3 +1,175 @@
+use aoc_rust::{
+    registry,
+    visualize::{self, DynVisualize, Mode, Player},
+    DynSolution, Problem,
+};
+use clap::Parser;
+use std::{
+    fs::read_to_string,
+    io, process,
+    time::{Duration, Instant},
+};
+
+/// run solutions against their inputs. e.g. `cargo run --release -- 2022 7`.
+/// with `--visualize` the simulation days are animated instead
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// year to run
+    #[clap(index = 1, value_name = "year")]
+    year: usize,
+
+    /// day to run, defaults to every day of the year
+    #[clap(index = 2, value_name = "day")]
+    day: Option<usize>,
+
+    /// watch the days that can be visualized play out rather than printing answers
+    #[clap(long)]
+    visualize: bool,
+
+    /// milliseconds each frame is shown for
+    #[clap(long, default_value_t = 50, requires = "visualize")]
+    delay: u64,
+
+    /// print every frame as plain text instead of animating, e.g. to pipe to a file
+    #[clap(long, requires = "visualize")]
+    dump: bool,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let now = Instant::now();
+    let result = f();
+    (result, now.elapsed())
+}
+
+/// prints an answer, indenting multi line answers under their label
+fn print_answer(label: &str, answer: &str, elapsed: Duration) {
+    if answer.contains('\n') {
+        println!("{label}: ({elapsed:.2?})");
+        for line in answer.lines() {
+            println!("    {line}");
+        }
+    } else {
+        println!("{label}: {answer} ({elapsed:.2?})");
+    }
+}
+
+/// the problem's input, or `None` once the failure is reported
+fn load(problem: Problem) -> Option<String> {
+    let path = problem.input_path();
+    match read_to_string(&path) {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!(
+                "Failed to read input \"{}\". {}",
+                path.as_ref().display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+fn run(solution: &dyn DynSolution) -> Duration {
+    let problem = solution.problem();
+    println!(
+        "🎄 {} day {:02}: {} 🎄",
+        problem.year, problem.day, problem.name
+    );
+
+    let path = problem.input_path();
+    let Some(input) = load(problem) else {
+        return Duration::ZERO;
+    };
+
+    let (parsed, elapsed_parse) = timed(|| solution.parse_input(&input));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Failed to parse input. {}", e.with_path(&path));
+            return elapsed_parse;
+        }
+    };
+    println!("parse: ({elapsed_parse:.2?})");
+
+    let (answer, elapsed_pt1) = timed(|| solution.run_pt1(parsed.as_ref()));
+    print_answer("part 1", &answer, elapsed_pt1);
+    let (answer, elapsed_pt2) = timed(|| solution.run_pt2(parsed.as_ref()));
+    print_answer("part 2", &answer, elapsed_pt2);
+    elapsed_parse + elapsed_pt1 + elapsed_pt2
+}
+
+/// plays a day's frames to stdout
+fn play(visualization: &dyn DynVisualize, mode: Mode) {
+    let problem = visualization.problem();
+    let Some(input) = load(problem) else {
+        return;
+    };
+    let parsed = match visualization.parse_input(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!(
+                "Failed to parse input. {}",
+                e.with_path(problem.input_path())
+            );
+            return;
+        }
+    };
+
+    let mut player = Player::new(io::stdout().lock(), mode);
+    let mut shown = Ok(());
+    visualization.visualize(parsed.as_ref(), &mut |frame| {
+        // the simulation runs on regardless, e.g. once a dump's pipe is closed
+        if shown.is_ok() {
+            shown = player.show(&frame);
+        }
+    });
+    if let Err(e) = shown {
+        eprintln!("Failed to show frame {}. {}", player.frames() + 1, e);
+    }
+}
+
 fn main() {
-    println!("Hello, world!");
+    let args = Args::parse();
+    if args.visualize {
+        let visualizations: Vec<Box<dyn DynVisualize>> = visualize::registry()
+            .into_iter()
+            .filter(|v| v.problem().year == args.year)
+            .filter(|v| args.day.is_none_or(|day| v.problem().day == day))
+            .collect();
+        if visualizations.is_empty() {
+            eprintln!("No visualization registered for the selection");
+            process::exit(1);
+        }
+        let mode = match args.dump {
+            true => Mode::Dump,
+            false => Mode::Animate {
+                delay: Duration::from_millis(args.delay),
+            },
+        };
+        for visualization in visualizations.iter() {
+            play(visualization.as_ref(), mode);
+        }
+        return;
+    }
+
+    let solutions: Vec<Box<dyn DynSolution>> = registry()
+        .into_iter()
+        .filter(|s| s.problem().year == args.year)
+        .filter(|s| args.day.is_none_or(|day| s.problem().day == day))
+        .collect();
+
+    if solutions.is_empty() {
+        match args.day {
+            Some(day) => eprintln!("No solution registered for {} day {}", args.year, day),
+            None => eprintln!("No solutions registered for {}", args.year),
+        }
+        process::exit(1);
+    }
+
+    let total: Duration = solutions.iter().map(|s| run(s.as_ref())).sum();
+    if solutions.len() > 1 {
+        println!("--------------------------------");
+        println!("total: {:.2?}", total);
+    }
 }
@@ -0,0 +1,361 @@
+//! number theory and statistics that keep coming back
+
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// the primitive integers, signed or not
+pub trait Integer:
+    Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `None` for `MIN` of a signed type, whose absolute value doesn't fit
+    fn checked_abs(self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! integer {
+    (signed $($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_abs(self) -> Option<Self> {
+                <$t>::checked_abs(self)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+    (unsigned $($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_abs(self) -> Option<Self> {
+                Some(self)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+integer!(signed i8, i16, i32, i64, i128, isize);
+integer!(unsigned u8, u16, u32, u64, u128, usize);
+
+/// greatest common divisor, never negative. `gcd(0, 0)` is 0.
+/// `None` if either is the `MIN` of a signed type
+pub fn gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a.checked_abs()?, b.checked_abs()?);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    Some(a)
+}
+
+/// least common multiple, never negative. 0 if either is 0.
+/// `None` if either is the `MIN` of a signed type, or the lcm doesn't fit
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    let (a, b) = (a.checked_abs()?, b.checked_abs()?);
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)?).checked_mul(b)
+}
+
+/// `(g, x, y)` such that `a * x + b * y == g`, with `g` the gcd of `a` and `b`
+pub fn extended_gcd<T: Integer + Neg<Output = T>>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let (mut old_t, mut t) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+    if old_r < T::ZERO {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// the `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+/// `None` unless `m` is positive
+pub fn mod_inv<T: Integer + Neg<Output = T>>(a: T, m: T) -> Option<T> {
+    if m <= T::ZERO {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a, m);
+    // `x % m` is in `-m..m`, so adding `m` to a negative one can't overflow
+    (g == T::ONE).then(|| match x % m {
+        x if x < T::ZERO => x + m,
+        x => x,
+    })
+}
+
+/// `base.pow(exp) % m` without overflowing. `None` if `m` is 0
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    Some(result as u64)
+}
+
+/// solves `x ≡ residue (mod modulus)` for every pair at once.
+/// returns the smallest non-negative `x` and the lcm of the moduli it repeats with,
+/// or `None` if the congruences contradict each other. the moduli needn't be coprime,
+/// but must be positive, and `None` is also returned if their lcm doesn't fit in an `i64`
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0i128, 1i64);
+    for &(residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+        let (residue, modulus) = (residue as i128, modulus as i128);
+        let (g, p, _) = extended_gcd(m as i128, modulus);
+        if (residue - x) % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = ((residue - x) / g % step * p % step + step) % step;
+        x += m as i128 * k;
+        // a divisor of `modulus`, so it fits
+        m = m.checked_mul(step as i64)?;
+        x = x.rem_euclid(m as i128);
+    }
+    Some((x as i64, m))
+}
+
+/// the largest `r` with `r * r <= n`
+pub fn isqrt(n: u64) -> u64 {
+    let mut r = (n as f64).sqrt() as u64;
+    // the float estimate can be off by one either way for large n
+    while r.checked_mul(r).is_none_or(|sq| sq > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).is_some_and(|sq| sq <= n) {
+        r += 1;
+    }
+    r
+}
+
+/// `1 + 2 + .. + n`
+pub fn triangle(n: u64) -> u64 {
+    n * (n + 1) / 2
+}
+
+/// the largest `k` with `triangle(k) <= n`
+pub fn triangle_root(n: u64) -> u64 {
+    // `8 * n + 1` overflows for large n, so estimate then correct in u128
+    let fits = |k: u64| k as u128 * (k as u128 + 1) / 2 <= n as u128;
+    let mut k = (2.0 * n as f64).sqrt() as u64;
+    while !fits(k) {
+        k -= 1;
+    }
+    while fits(k + 1) {
+        k += 1;
+    }
+    k
+}
+
+/// the lower median, `None` if there are no values
+pub fn median<T: Ord + Clone>(values: &[T]) -> Option<T> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut values = values.to_vec();
+    let mid = (values.len() - 1) / 2;
+    let (_, median, _) = values.select_nth_unstable(mid);
+    Some(median.clone())
+}
+
+/// `None` if there are no values
+pub fn mean<T: Integer>(values: &[T]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let sum: f64 = values.iter().map(|v| v.to_f64()).sum();
+    Some(sum / values.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn examples() {
+        assert_eq!(gcd(12, 18), Some(6));
+        assert_eq!(gcd(-12, 18), Some(6));
+        assert_eq!(gcd(0u32, 0), Some(0));
+        assert_eq!(gcd(i64::MIN, 3), None);
+        assert_eq!(gcd(3, i64::MIN), None);
+        assert_eq!(gcd(i64::MAX, i64::MAX), Some(i64::MAX));
+        assert_eq!(lcm(4usize, 6), Some(12));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(mod_pow(2, 10, 0), None);
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(2, 4), None);
+        assert_eq!(mod_inv(1, 0), None);
+        assert_eq!(mod_inv(3, -11), None);
+        assert_eq!(mod_inv(-1, i64::MAX), Some(i64::MAX - 1));
+        assert_eq!(crt(&[(0, 3), (3, 4), (4, 5)]), Some((39, 60)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt(&[(1, 4), (0, 0)]), None);
+        assert_eq!(crt(&[(1, -4)]), None);
+        assert_eq!(crt(&[(0, i64::MAX), (0, i64::MAX - 1)]), None);
+        assert_eq!(crt(&[(1, i64::MAX)]), Some((1, i64::MAX)));
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(triangle_root(triangle(44)), 44);
+        assert_eq!(triangle_root(u64::MAX), 6_074_000_999);
+        assert_eq!(median(&[16, 1, 2, 0, 4, 2, 7, 1, 2, 14]), Some(2));
+        assert_eq!(median::<u8>(&[]), None);
+        assert_eq!(mean(&[1, 2, 3, 4]), Some(2.5));
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_divides_both(a in 1i64..1_000_000, b in 1i64..1_000_000) {
+            let g = gcd(a, b).unwrap();
+            prop_assert_eq!(a % g, 0);
+            prop_assert_eq!(b % g, 0);
+            prop_assert_eq!(g * lcm(a, b).unwrap(), a * b);
+        }
+
+        #[test]
+        fn gcd_of_min_is_none(a in any::<i64>()) {
+            prop_assert_eq!(gcd(i64::MIN, a), None);
+            prop_assert_eq!(gcd(a, i64::MIN), None);
+            prop_assert_eq!(lcm(a, i64::MIN), None);
+        }
+
+        #[test]
+        fn lcm_is_none_when_it_overflows(a in any::<u64>(), b in any::<u64>()) {
+            let exact = match gcd(a, b).unwrap() {
+                0 => 0,
+                g => a as u128 / g as u128 * b as u128,
+            };
+            prop_assert_eq!(lcm(a, b).map(u128::from), (exact <= u64::MAX as u128).then_some(exact));
+        }
+
+        #[test]
+        fn extended_gcd_is_bezout(
+            a in -1_000_000i64..1_000_000,
+            b in -1_000_000i64..1_000_000,
+        ) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(Some(g), gcd(a, b));
+            prop_assert_eq!(a * x + b * y, g);
+        }
+
+        #[test]
+        fn mod_inv_inverts(a in -10_000i64..10_000, m in -10i64..10_000) {
+            match mod_inv(a, m) {
+                Some(inv) => {
+                    prop_assert!((0..m).contains(&inv));
+                    prop_assert_eq!((a * inv).rem_euclid(m), 1 % m);
+                }
+                None => prop_assert!(m <= 0 || gcd(a, m) != Some(1)),
+            }
+        }
+
+        #[test]
+        fn mod_pow_matches_repeated_multiplication(
+            base in any::<u64>(),
+            exp in 0u64..64,
+            m in 1u64..u64::MAX,
+        ) {
+            let (base, m) = (base as u128, m as u128);
+            let naive = (0..exp).fold(1 % m, |acc, _| acc * (base % m) % m);
+            prop_assert_eq!(mod_pow(base as u64, exp, m as u64), Some(naive as u64));
+        }
+
+        #[test]
+        fn crt_satisfies_every_congruence(
+            pairs in prop::collection::vec((0i64..1000, 1i64..50), 1..5),
+        ) {
+            match crt(&pairs) {
+                Some((x, m)) => {
+                    prop_assert!((0..m).contains(&x));
+                    for (r, n) in pairs {
+                        prop_assert_eq!(x % n, r % n);
+                    }
+                }
+                // no solution means some pair of congruences clash
+                None => {
+                    let clash = pairs.iter().enumerate().any(|(i, &(r1, n1))| {
+                        pairs[i + 1..]
+                            .iter()
+                            .any(|&(r2, n2)| (r1 - r2) % gcd(n1, n2).unwrap() != 0)
+                    });
+                    prop_assert!(clash);
+                }
+            }
+        }
+
+        #[test]
+        fn isqrt_is_floor(n in any::<u64>()) {
+            let r = isqrt(n) as u128;
+            prop_assert!(r * r <= n as u128);
+            prop_assert!((r + 1) * (r + 1) > n as u128);
+        }
+
+        #[test]
+        fn triangle_root_is_floor(n in any::<u64>()) {
+            // `triangle` itself overflows near the top of u64
+            let triangle = |k: u64| k as u128 * (k as u128 + 1) / 2;
+            let k = triangle_root(n);
+            prop_assert!(triangle(k) <= n as u128 && triangle(k + 1) > n as u128);
+        }
+
+        #[test]
+        fn median_splits_values(values in prop::collection::vec(any::<i32>(), 1..50)) {
+            let m = median(&values).unwrap();
+            let below = values.iter().filter(|&&v| v < m).count();
+            let above = values.iter().filter(|&&v| v > m).count();
+            prop_assert!(below <= (values.len() - 1) / 2);
+            prop_assert!(above <= values.len() / 2);
+        }
+
+        #[test]
+        fn mean_is_bounded(values in prop::collection::vec(-1000i32..1000, 1..50)) {
+            let mean = mean(&values).unwrap();
+            prop_assert!(*values.iter().min().unwrap() as f64 <= mean);
+            prop_assert!(mean <= *values.iter().max().unwrap() as f64);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! reading the block capitals that some puzzles draw instead of printing an answer
+
+use crate::{error::ParseError, grid::Grid};
+
+/// a fixed width font, each glyph drawn with `#` for a lit pixel
+#[derive(Debug, Clone, Copy)]
+pub struct Font {
+    pub width: usize,
+    pub height: usize,
+    /// blank columns between letters
+    pub spacing: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+/// the 4x6 letters of e.g. 2022 day 10 and 2021 day 13
+pub const SMALL: Font = Font {
+    width: 4,
+    height: 6,
+    spacing: 1,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+/// the 6x10 letters of 2018 day 10
+#[rustfmt::skip]
+pub const LARGE: Font = Font {
+    width: 6,
+    height: 10,
+    spacing: 2,
+    glyphs: &[
+        ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+        ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+        ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+        ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+        ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+        ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+        ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+        ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+        ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+    ],
+};
+
+impl Font {
+    /// the font whose letters are `height` pixels tall
+    pub fn for_height(height: usize) -> Option<Font> {
+        [SMALL, LARGE].into_iter().find(|f| f.height == height)
+    }
+
+    /// the letters drawn left to right across `grid`, starting in its first column.
+    /// pixels past the edge of the grid are dark, so the last letter's blank columns can be cut off
+    pub fn read(&self, grid: &Grid<bool>) -> Result<String, ParseError> {
+        (0..grid.width())
+            .step_by(self.width + self.spacing)
+            .enumerate()
+            .map(|(i, x)| self.letter(grid, x).ok_or(ParseError::UnknownLetter(i)))
+            .collect()
+    }
+
+    /// the letter with its top left pixel at `(x, 0)`
+    fn letter(&self, grid: &Grid<bool>, x: usize) -> Option<char> {
+        let lit = |pos| grid.get(pos).copied().unwrap_or_default();
+        self.glyphs
+            .iter()
+            .find(|(_, rows)| {
+                rows.iter().enumerate().all(|(y, row)| {
+                    row.chars()
+                        .enumerate()
+                        .all(|(dx, c)| (c == '#') == lit((x + dx, y)))
+                })
+            })
+            .map(|&(c, _)| c)
+    }
+}
+
+/// reads `grid` in whichever font is as tall as it is
+pub fn read(grid: &Grid<bool>) -> Result<String, ParseError> {
+    Font::for_height(grid.height())
+        .ok_or(ParseError::UnknownFont(grid.height()))?
+        .read(grid)
+}
+
+/// reads a drawing with one line per row, where `#` or `█` is a lit pixel and
+/// anything else is dark. the lines needn't all be the same length
+pub fn read_str(drawing: &str) -> Result<String, ParseError> {
+    let rows: Vec<Vec<char>> = drawing.lines().map(|l| l.chars().collect()).collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let grid = Grid::from_fn(width, rows.len(), |(x, y)| {
+        matches!(rows[y].get(x), Some('#' | '█'))
+    });
+    read(&grid)
+}
+
+/// `#` and `.` one line per row, which `read_str` reads back.
+/// for showing a drawing that couldn't be read
+pub fn draw(grid: &Grid<bool>) -> String {
+    grid.map(|&lit| if lit { '#' } else { '.' }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `text` in `font`, with the blank columns after the last letter too
+    fn render(font: Font, text: &str) -> Grid<bool> {
+        let pitch = font.width + font.spacing;
+        let letters: Vec<char> = text.chars().collect();
+        Grid::from_fn(letters.len() * pitch, font.height, |(x, y)| {
+            let (_, rows) = font
+                .glyphs
+                .iter()
+                .find(|g| g.0 == letters[x / pitch])
+                .unwrap();
+            rows[y].chars().nth(x % pitch) == Some('#')
+        })
+    }
+
+    #[test]
+    fn small() {
+        let drawing = "\
+####.#..#.###..####.###....##..##..#....
+#....#..#.#..#....#.#..#....#.#..#.#....
+###..####.#..#...#..#..#....#.#....#....
+#....#..#.###...#...###.....#.#.##.#....
+#....#..#.#....#....#....#..#.#..#.#....
+####.#..#.#....####.#.....##...###.####.";
+        assert_eq!(read_str(drawing).unwrap(), "EHPZPJGL");
+        // as a 2021 day 13 fold leaves it, without the last blank column
+        let trimmed: Vec<&str> = drawing.lines().map(|l| &l[..39]).collect();
+        assert_eq!(read_str(&trimmed.join("\n")).unwrap(), "EHPZPJGL");
+        let blocks = drawing.replace('#', "█").replace('.', " ");
+        assert_eq!(read_str(&blocks).unwrap(), "EHPZPJGL");
+    }
+
+    #[test]
+    fn every_glyph() {
+        for font in [SMALL, LARGE] {
+            let text: String = font.glyphs.iter().map(|g| g.0).collect();
+            let grid = render(font, &text);
+            assert_eq!(read(&grid).unwrap(), text);
+            assert_eq!(read_str(&draw(&grid)).unwrap(), text);
+        }
+    }
+
+    #[test]
+    fn unreadable() {
+        let mut grid = render(SMALL, "ABC");
+        grid[(8, 0)] = true;
+        assert!(matches!(read(&grid), Err(ParseError::UnknownLetter(1))));
+        let short = Grid::new(10, 5, false);
+        assert!(matches!(read(&short), Err(ParseError::UnknownFont(5))));
+    }
+}
@@ -0,0 +1,114 @@
+//! pulls what scaffold needs out of a puzzle page, without an html parser
+
+/// the parts of a puzzle page used to scaffold a day
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    /// e.g. "Calorie Counting"
+    pub name: Option<String>,
+    /// every `<pre><code>` block, in page order
+    pub examples: Vec<String>,
+    /// the last highlighted `<code><em>` in each part's description, usually the example answer.
+    /// `None` for a part without one, so part 2's answer is always at index 1
+    pub answers: Vec<Option<String>>,
+}
+
+impl Puzzle {
+    pub fn from_html(html: &str) -> Self {
+        let name = between(html, "<h2>", "</h2>")
+            .next()
+            .and_then(|title| title.split_once(": "))
+            .map(|(_, name)| unescape(name.trim_end_matches('-').trim()));
+        let examples = between(html, "<pre><code>", "</code></pre>")
+            .map(|block| unescape(&strip_tags(block)))
+            .collect();
+        let answers = between(html, "<article class=\"day-desc\">", "</article>")
+            .map(|article| {
+                between(article, "<code><em>", "</em></code>")
+                    .last()
+                    .map(|answer| unescape(&strip_tags(answer)))
+            })
+            .collect();
+        Puzzle {
+            name,
+            examples,
+            answers,
+        }
+    }
+}
+
+/// every non overlapping stretch of `s` between `start` and `end`
+fn between<'a>(s: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    s.split(start)
+        .skip(1)
+        .filter_map(move |rest| rest.split_once(end).map(|(inner, _)| inner))
+}
+
+/// drops inline markup like the `<em>` aoc uses to highlight parts of an example
+fn strip_tags(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_tag = false;
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => (),
+        }
+    }
+    out
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>For example:</p>
+<pre><code>1000
+2000
+
+<em>3000</em>
+</code></pre>
+<p>The Elf carrying the most is carrying <code><em>6000</em></code> Calories.</p>
+<pre><code>a &lt; b &amp;&amp; c &gt; d
+</code></pre>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In the example above this is <code>10</code>, then <code><em>45000</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn from_html() {
+        let puzzle = Puzzle::from_html(PAGE);
+        assert_eq!(puzzle.name.as_deref(), Some("Calorie Counting"));
+        assert_eq!(
+            puzzle.examples,
+            vec!["1000\n2000\n\n3000\n", "a < b && c > d\n"]
+        );
+        assert_eq!(
+            puzzle.answers,
+            vec![Some("6000".to_owned()), Some("45000".to_owned())]
+        );
+    }
+
+    #[test]
+    fn unanswered_part() {
+        let page = PAGE.replace("<code><em>6000</em></code>", "6000");
+        let puzzle = Puzzle::from_html(&page);
+        assert_eq!(puzzle.answers, vec![None, Some("45000".to_owned())]);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Puzzle::from_html("<html></html>"), Puzzle::default());
+    }
+}
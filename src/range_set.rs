@@ -0,0 +1,271 @@
+//! sets of integers stored as sorted, disjoint inclusive ranges
+
+use std::{
+    cmp::{max, min},
+    ops::{Add, RangeInclusive, Sub},
+};
+
+/// the integers a range set can hold
+pub trait Discrete: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! discrete {
+    ($($t:ty),*) => {$(
+        impl Discrete for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn saturating_add(self, rhs: Self) -> Self {
+                <$t>::saturating_add(self, rhs)
+            }
+
+            fn saturating_sub(self, rhs: Self) -> Self {
+                <$t>::saturating_sub(self, rhs)
+            }
+        }
+    )*};
+}
+
+discrete!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// ranges are kept sorted, and overlapping or touching ranges are merged on insert.
+/// so two sets holding the same integers compare equal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: Discrete> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// adds every integer in `range`. an empty range is a no-op
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        let mut placed = false;
+        // `e < start` means `e + 1` can't overflow, and likewise for `end < s`
+        for &(s, e) in &self.ranges {
+            if e < start && e + T::ONE < start {
+                ranges.push((s, e));
+            } else if end < s && end + T::ONE < s {
+                if !placed {
+                    ranges.push((start, end));
+                    placed = true;
+                }
+                ranges.push((s, e));
+            } else {
+                start = min(start, s);
+                end = max(end, e);
+            }
+        }
+        if !placed {
+            ranges.push((start, end));
+        }
+        self.ranges = ranges;
+    }
+
+    /// drops every integer in `range`
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        for &(s, e) in &self.ranges {
+            if e < start || s > end {
+                ranges.push((s, e));
+                continue;
+            }
+            if s < start {
+                ranges.push((s, start - T::ONE));
+            }
+            if e > end {
+                ranges.push((end + T::ONE, e));
+            }
+        }
+        self.ranges = ranges;
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges.iter().any(|&(s, e)| s <= value && value <= e)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            let (start, end) = (max(a.0, b.0), min(a.1, b.1));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    /// every integer in `bounds` that isn't in the set
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> Self {
+        let mut complement = RangeSet::from(bounds);
+        for range in self.iter() {
+            complement.remove(range);
+        }
+        complement
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.intersection(other) == *self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// the number of integers in the set, saturating at `T`'s maximum if it doesn't fit
+    pub fn len(&self) -> T {
+        self.ranges.iter().fold(T::ZERO, |len, &(s, e)| {
+            len.saturating_add(e.saturating_sub(s).saturating_add(T::ONE))
+        })
+    }
+
+    /// the disjoint ranges, in order
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// the missing stretches between the first and last range, in order
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        // ranges never touch, so each gap holds at least one integer and neither end overflows
+        self.ranges
+            .windows(2)
+            .map(|w| w[0].1 + T::ONE..=w[1].0 - T::ONE)
+    }
+}
+
+impl<T: Discrete> From<RangeInclusive<T>> for RangeSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut set = RangeSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges<T: Discrete>(set: &RangeSet<T>) -> Vec<RangeInclusive<T>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn insert_merges() {
+        let mut set: RangeSet<i32> = [1..=3, 10..=12, 6..=7].into_iter().collect();
+        assert_eq!(ranges(&set), vec![1..=3, 6..=7, 10..=12]);
+        set.insert(4..=4);
+        assert_eq!(ranges(&set), vec![1..=4, 6..=7, 10..=12]);
+        set.insert(5..=5);
+        assert_eq!(ranges(&set), vec![1..=7, 10..=12]);
+        set.insert(0..=11);
+        assert_eq!(ranges(&set), vec![0..=12]);
+        // reversed, so empty
+        set.insert(RangeInclusive::new(5, 2));
+        assert_eq!(set.len(), 13);
+    }
+
+    #[test]
+    fn remove_splits() {
+        let mut set = RangeSet::from(0..=10usize);
+        set.remove(3..=4);
+        set.remove(10..=20);
+        assert_eq!(ranges(&set), vec![0..=2, 5..=9]);
+        assert!(set.contains(5) && !set.contains(3));
+        assert_eq!(set.len(), 8);
+        set.remove(0..=9);
+        assert!(set.is_empty());
+        assert_eq!(set.len(), 0);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: RangeSet<isize> = [-5..=0, 4..=8].into_iter().collect();
+        let b: RangeSet<isize> = [-1..=5, 8..=9].into_iter().collect();
+        assert_eq!(ranges(&a.union(&b)), vec![-5..=9]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![-1..=0, 4..=5, 8..=8]);
+        assert_eq!(
+            ranges(&a.complement(-10..=10)),
+            vec![-10..=-6, 1..=3, 9..=10]
+        );
+        assert_eq!(ranges(&b.gaps().collect()), vec![6..=7]);
+        assert!(RangeSet::from(5..=6).is_subset(&a));
+        assert!(!RangeSet::from(3..=6).is_subset(&a));
+    }
+
+    #[test]
+    fn far_apart() {
+        let mut set: RangeSet<i8> = [-100..=-90, 100..=110].into_iter().collect();
+        assert_eq!(ranges(&set), vec![-100..=-90, 100..=110]);
+        assert_eq!(set.len(), 22);
+        assert_eq!(ranges(&set.gaps().collect()), vec![-89..=99]);
+        set.insert(-89..=99);
+        assert_eq!(ranges(&set), vec![-100..=110]);
+        assert_eq!(set.len(), i8::MAX);
+    }
+
+    #[test]
+    fn type_bounds() {
+        let mut set: RangeSet<i8> = [i8::MAX..=i8::MAX, i8::MIN..=i8::MIN].into_iter().collect();
+        assert_eq!(ranges(&set), vec![i8::MIN..=i8::MIN, i8::MAX..=i8::MAX]);
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            ranges(&set.gaps().collect()),
+            vec![i8::MIN + 1..=i8::MAX - 1]
+        );
+        set.insert(i8::MIN..=i8::MAX);
+        assert_eq!(ranges(&set), vec![i8::MIN..=i8::MAX]);
+        assert_eq!(set.len(), i8::MAX);
+        set.remove(i8::MIN..=-1);
+        assert_eq!(set.len(), i8::MAX);
+
+        let top = RangeSet::from(u8::MAX - 1..=u8::MAX);
+        assert_eq!(ranges(&top.complement(0..=u8::MAX)), vec![0..=u8::MAX - 2]);
+    }
+}
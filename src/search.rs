@@ -0,0 +1,211 @@
+//! graph searches over a closure that yields each node's neighbours.
+//! every search accepts several starts, as if they were all joined to one virtual start
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// the fewest steps from any of `starts` to a node satisfying `is_goal`, with the path taken.
+/// the path includes both ends
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(usize, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if parents.insert(start.clone(), None).is_none() {
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            let path = path(&parents, node);
+            return Some((path.len() - 1, path));
+        }
+        for next in neighbours(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// every node reachable from `starts`, starts included
+pub fn reachable<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut queue: VecDeque<N> = starts.into_iter().collect();
+    seen.extend(queue.iter().cloned());
+    while let Some(node) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if seen.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+    seen
+}
+
+/// the cheapest way from any of `starts` to a node satisfying `is_goal`.
+/// `neighbours` yields each neighbour with the cost of stepping to it
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// dijkstra guided by `heuristic`, which must never overestimate the remaining cost
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // the heap holds indices into `nodes` so nodes don't need to be `Ord`
+    let mut nodes = Vec::new();
+    let mut best: HashMap<N, (C, Option<N>)> = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+    for start in starts {
+        if best.insert(start.clone(), (C::default(), None)).is_none() {
+            frontier.push(Reverse((heuristic(&start), C::default(), nodes.len())));
+            nodes.push(start);
+        }
+    }
+    while let Some(Reverse((_, cost, i))) = frontier.pop() {
+        let node = nodes[i].clone();
+        if best[&node].0 < cost {
+            // a cheaper way here was already expanded
+            continue;
+        }
+        if is_goal(&node) {
+            let parents = best.into_iter().map(|(n, (_, p))| (n, p)).collect();
+            return Some((cost, path(&parents, node)));
+        }
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if matches!(best.get(&next), Some(&(c, _)) if c <= next_cost) {
+                continue;
+            }
+            best.insert(next.clone(), (next_cost, Some(node.clone())));
+            frontier.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+    None
+}
+
+/// walks the parent links back from `end` to a start
+fn path<N: Eq + Hash + Clone>(parents: &HashMap<N, Option<N>>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Pos};
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.";
+
+    fn maze() -> Grid<char> {
+        MAZE.parse().unwrap()
+    }
+
+    fn open(grid: &Grid<char>, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        grid.neighbours4(pos).filter(|&n| grid[n] == '.')
+    }
+
+    #[test]
+    fn bfs_path() {
+        let grid = maze();
+        let end = (6, 0);
+        let (steps, path) = bfs([(0, 0)], |&p| open(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(steps, 12);
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), ((0, 0), end));
+        assert!(path
+            .windows(2)
+            .all(|w| grid.neighbours4(w[0]).any(|n| n == w[1])));
+    }
+
+    #[test]
+    fn unreachable() {
+        let grid = maze();
+        assert_eq!(
+            bfs([(0, 0)], |&p| open(&grid, p), |&p| p == (1, 0)).map(|r| r.0),
+            Some(1)
+        );
+        assert_eq!(bfs([(0, 0)], |&p| open(&grid, p), |&p| p == (2, 0)), None);
+        let costs = |&p: &Pos| open(&grid, p).map(|n| (n, 1));
+        assert_eq!(dijkstra([(0, 0)], costs, |&p| p == (2, 0)), None);
+    }
+
+    #[test]
+    fn multi_source() {
+        let grid = maze();
+        let starts = [(0, 0), (6, 3)];
+        let (steps, path) = bfs(starts, |&p| open(&grid, p), |&p| p == (6, 0)).unwrap();
+        assert_eq!((steps, path[0]), (3, (6, 3)));
+        let region = reachable([(0, 0)], |&p| open(&grid, p).collect::<Vec<_>>());
+        assert_eq!(region.len(), grid.values().filter(|&&c| c == '.').count());
+    }
+
+    #[test]
+    fn weighted() {
+        let grid: Grid<u32> = Grid::parse_with("1163\n1381\n2136", |c| c.to_digit(10)).unwrap();
+        let end = (3, 2);
+        let costs = |&p: &Pos| {
+            grid.neighbours4(p)
+                .map(|n| (n, grid[n]))
+                .collect::<Vec<_>>()
+        };
+        let (cost, path) = dijkstra([(0, 0)], costs, |&p| p == end).unwrap();
+        assert_eq!(cost, 13);
+        assert_eq!(path.iter().skip(1).map(|&p| grid[p]).sum::<u32>(), cost);
+
+        let manhattan = |&(x, y): &Pos| (end.0 - x + end.1 - y) as u32;
+        let (a_cost, _) = astar([(0, 0)], costs, manhattan, |&p| p == end).unwrap();
+        assert_eq!(a_cost, cost);
+    }
+}
@@ -0,0 +1,163 @@
+//! stepping a state forward, and skipping ahead once it starts repeating
+
+use std::{collections::HashMap, hash::Hash};
+
+/// the state after `steps` applications of `step`
+pub fn run<S>(mut state: S, steps: usize, mut step: impl FnMut(&mut S)) -> S {
+    for _ in 0..steps {
+        step(&mut state);
+    }
+    state
+}
+
+/// steps `state` until `done` holds, returning the number of steps taken.
+/// `done` is checked after each step, so at least one step is always taken
+pub fn run_until<S>(
+    state: &mut S,
+    mut step: impl FnMut(&mut S),
+    mut done: impl FnMut(&S) -> bool,
+) -> usize {
+    let mut steps = 0;
+    loop {
+        step(state);
+        steps += 1;
+        if done(state) {
+            return steps;
+        }
+    }
+}
+
+/// steps `state` for as long as `step` reports it made progress, returning how many steps did.
+/// the last step, which reports none, still runs but isn't counted
+pub fn run_while<S>(state: &mut S, mut step: impl FnMut(&mut S) -> bool) -> usize {
+    let mut steps = 0;
+    while step(state) {
+        steps += 1;
+    }
+    steps
+}
+
+/// where a simulation first revisits a state
+#[derive(Debug, Clone)]
+pub struct Cycle<S> {
+    /// the step at which the loop is entered
+    pub start: usize,
+    pub len: usize,
+    /// every state from the initial one up to the first repeat, exclusive
+    pub history: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// the state after `n` steps, for any `n`
+    pub fn nth(&self, n: usize) -> &S {
+        if n < self.start {
+            &self.history[n]
+        } else {
+            &self.history[self.start + (n - self.start) % self.len]
+        }
+    }
+}
+
+/// steps until a state repeats. loops forever if none ever does
+pub fn find_cycle<S: Hash + Eq + Clone>(mut state: S, mut step: impl FnMut(&mut S)) -> Cycle<S> {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let len = history.len() - start;
+            return Cycle {
+                start,
+                len,
+                history,
+            };
+        }
+        seen.insert(state.clone(), history.len());
+        history.push(state.clone());
+        step(&mut state);
+    }
+}
+
+/// the state after `n` steps, skipping the rest of the way once a state repeats.
+/// so `n` can be far too large to step through, e.g. 1e12
+pub fn nth<S: Hash + Eq + Clone>(mut state: S, n: usize, mut step: impl FnMut(&mut S)) -> S {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let len = i - start;
+            return history.swap_remove(start + (n - start) % len);
+        }
+        seen.insert(state.clone(), i);
+        history.push(state.clone());
+        step(&mut state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, then round 3, 4, 5, 6 forever
+    fn tail_and_loop(s: &mut usize) {
+        *s = if *s < 6 { *s + 1 } else { 3 };
+    }
+
+    #[test]
+    fn run_steps() {
+        assert_eq!(run(1u64, 10, |s| *s *= 2), 1024);
+        let mut s = 1u64;
+        assert_eq!(run_until(&mut s, |s| *s *= 3, |&s| s > 100), 5);
+        assert_eq!(s, 243);
+        let mut s = 10u64;
+        let take_three = |s: &mut u64| {
+            let enough = *s >= 3;
+            if enough {
+                *s -= 3;
+            }
+            enough
+        };
+        assert_eq!(run_while(&mut s, take_three), 3);
+        assert_eq!(s, 1);
+        assert_eq!(run_while(&mut s, |_| false), 0);
+    }
+
+    #[test]
+    fn cycle() {
+        let cycle = find_cycle(0, tail_and_loop);
+        assert_eq!((cycle.start, cycle.len), (3, 4));
+        assert_eq!(cycle.history, vec![0, 1, 2, 3, 4, 5, 6]);
+        for n in 0..30 {
+            assert_eq!(*cycle.nth(n), run(0, n, tail_and_loop));
+            assert_eq!(nth(0, n, tail_and_loop), run(0, n, tail_and_loop));
+        }
+        assert_eq!(*cycle.nth(1_000_000_000_000), 4);
+        assert_eq!(nth(0, 1_000_000_000_000, tail_and_loop), 4);
+    }
+
+    #[test]
+    fn extrapolate_grid() {
+        // a glider on a 5x5 torus is back where it began every 20 steps
+        let glider: Vec<(i32, i32)> = vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let life = |cells: &mut Vec<(i32, i32)>| {
+            let alive = |x: i32, y: i32| cells.contains(&(x.rem_euclid(5), y.rem_euclid(5)));
+            let mut next = Vec::new();
+            for y in 0..5 {
+                for x in 0..5 {
+                    let n = (-1..=1)
+                        .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                        .filter(|&(dx, dy)| (dx, dy) != (0, 0) && alive(x + dx, y + dy))
+                        .count();
+                    if n == 3 || (n == 2 && alive(x, y)) {
+                        next.push((x, y));
+                    }
+                }
+            }
+            *cells = next;
+        };
+        let cycle = find_cycle(glider.clone(), life);
+        assert_eq!((cycle.start, cycle.len), (0, 20));
+        let far = nth(glider.clone(), 1_000_000_000_007, life);
+        assert_eq!(far, run(glider, 7, life));
+    }
+}
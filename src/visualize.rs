@@ -0,0 +1,241 @@
+//! watching a simulation play out in the terminal, a frame per step
+
+use crate::{aoc2021, aoc2022, downcast, grid::Grid, DynSolution, Solution};
+use std::{
+    any::Any,
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+/// the terminal colours a cell can be drawn in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Color {
+    /// the ANSI code that sets it as the foreground
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 97,
+            Color::Grey => 90,
+        }
+    }
+}
+
+/// a character of a frame and the colour to draw it in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub c: char,
+    pub color: Option<Color>,
+}
+
+impl Cell {
+    /// a dim `.`, as the puzzles draw empty space
+    pub const EMPTY: Cell = Cell::new('.', Color::Grey);
+
+    pub const fn new(c: char, color: Color) -> Self {
+        Cell {
+            c,
+            color: Some(color),
+        }
+    }
+
+    /// in the terminal's own colour
+    pub const fn plain(c: char) -> Self {
+        Cell { c, color: None }
+    }
+}
+
+/// the state after one step, with a caption above it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub cells: Grid<Cell>,
+}
+
+impl Frame {
+    /// builds each cell from its position
+    pub fn from_fn(
+        caption: impl Into<String>,
+        width: usize,
+        height: usize,
+        f: impl FnMut((usize, usize)) -> Cell,
+    ) -> Self {
+        Frame {
+            caption: caption.into(),
+            cells: Grid::from_fn(width, height, f),
+        }
+    }
+
+    /// the caption then a line per row, without colour
+    pub fn text(&self) -> String {
+        let rows = self.cells.map(|cell| cell.c).to_string();
+        format!("{}\n{}", self.caption, rows)
+    }
+
+    /// as `text`, with each coloured cell wrapped in escape codes
+    pub fn ansi(&self) -> String {
+        let mut s = format!("{}\n", self.caption);
+        for (y, row) in self.cells.rows().enumerate() {
+            if y > 0 {
+                s.push('\n');
+            }
+            for cell in row {
+                match cell.color {
+                    Some(color) => s.push_str(&format!("\x1b[{}m{}\x1b[0m", color.code(), cell.c)),
+                    None => s.push(cell.c),
+                }
+            }
+        }
+        s
+    }
+}
+
+/// a solution whose working can be watched
+pub trait Visualize: Solution {
+    /// solves `parsed` again, calling `emit` with each step along the way
+    fn visualize(parsed: &Self::Parsed, emit: &mut dyn FnMut(Frame));
+}
+
+/// object safe view of a [`Visualize`], as [`DynSolution`] is of a [`Solution`]
+pub trait DynVisualize: DynSolution {
+    /// panics if `parsed` didn't come from this solution's `parse_input`
+    fn visualize(&self, parsed: &dyn Any, emit: &mut dyn FnMut(Frame));
+}
+
+impl<S: Visualize> DynVisualize for S {
+    fn visualize(&self, parsed: &dyn Any, emit: &mut dyn FnMut(Frame)) {
+        S::visualize(downcast::<S>(parsed), emit)
+    }
+}
+
+/// every day that can be visualized, ordered by year then day
+pub fn registry() -> Vec<Box<dyn DynVisualize>> {
+    vec![
+        Box::new(aoc2021::day11::Day11),
+        Box::new(aoc2021::day17::Day17),
+        Box::new(aoc2022::day09::Day09),
+        Box::new(aoc2022::day10::Day10),
+        Box::new(aoc2022::day14::Day14),
+    ]
+}
+
+/// look up a single day's visualization
+pub fn find(year: usize, day: usize) -> Option<Box<dyn DynVisualize>> {
+    registry().into_iter().find(|v| {
+        let problem = v.problem();
+        problem.year == year && problem.day == day
+    })
+}
+
+/// how frames are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// redrawn in place in colour, holding each for `delay`
+    Animate { delay: Duration },
+    /// every frame as plain text, one after another
+    Dump,
+}
+
+/// writes frames to `out` as they arrive
+pub struct Player<W: Write> {
+    out: W,
+    mode: Mode,
+    frames: usize,
+}
+
+impl<W: Write> Player<W> {
+    pub fn new(out: W, mode: Mode) -> Self {
+        Player {
+            out,
+            mode,
+            frames: 0,
+        }
+    }
+
+    /// how many frames have been shown
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn show(&mut self, frame: &Frame) -> io::Result<()> {
+        match self.mode {
+            Mode::Animate { delay } => {
+                // clear once, then draw over the last frame
+                if self.frames == 0 {
+                    write!(self.out, "\x1b[2J")?;
+                }
+                write!(self.out, "\x1b[H{}\x1b[J", frame.ansi())?;
+                self.out.flush()?;
+                thread::sleep(delay);
+            }
+            Mode::Dump => writeln!(self.out, "{}\n", frame.text())?,
+        }
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    fn sample() -> Frame {
+        Frame::from_fn("step 1", 3, 2, |(x, y)| match (x, y) {
+            (0, 0) => Cell::new('#', Color::Red),
+            (2, 1) => Cell::plain('o'),
+            _ => Cell::EMPTY,
+        })
+    }
+
+    #[test]
+    fn render() {
+        let frame = sample();
+        assert_eq!(frame.text(), "step 1\n#..\n..o");
+        assert_eq!(
+            frame.ansi(),
+            "step 1\n\x1b[31m#\x1b[0m\x1b[90m.\x1b[0m\x1b[90m.\x1b[0m\n\x1b[90m.\x1b[0m\x1b[90m.\x1b[0mo"
+        );
+    }
+
+    #[test]
+    fn dump() {
+        let mut player = Player::new(Vec::new(), Mode::Dump);
+        player.show(&sample()).unwrap();
+        player.show(&sample()).unwrap();
+        assert_eq!(player.frames(), 2);
+        let out = String::from_utf8(player.out).unwrap();
+        assert_eq!(out, "step 1\n#..\n..o\n\nstep 1\n#..\n..o\n\n");
+    }
+
+    /// every visualization runs on its first example, and its frames keep one size
+    #[test]
+    fn examples() {
+        for vis in registry() {
+            let problem = vis.problem();
+            let data = read_to_string(problem.example_path("_1")).unwrap();
+            let parsed = vis.parse_input(&data).unwrap();
+            let mut frames = Vec::new();
+            vis.visualize(parsed.as_ref(), &mut |frame| frames.push(frame));
+            assert!(frames.len() > 1, "{} day {}", problem.year, problem.day);
+            let size = |f: &Frame| (f.cells.width(), f.cells.height());
+            assert!(frames.iter().all(|f| size(f) == size(&frames[0])));
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{error::Error, Problem, Solution};
+/// problem: https://adventofcode.com/$YEAR/day/$DAY
+/// input: https://adventofcode.com/$YEAR/day/$DAY/input
+
+pub const P: Problem = Problem {
+    year: $YEAR,
+    day: $DAY,
+    name: "$NAME",
+};
+
+fn pt1(parsed: &[Vec<usize>]) -> usize {
+    todo!()
+}
+
+fn pt2(parsed: &[Vec<usize>]) -> usize {
+    todo!()
+}
+
+/// blank line separated chunks of one value per line
+fn parse(input: &str) -> Result<Vec<Vec<usize>>, Error> {
+    crate::parse_chunks::<String>(input)?
+        .iter()
+        .map(|chunk| crate::parse_one_per_line(chunk))
+        .collect()
+}
+
+pub struct Day$PADDED_DAY;
+
+impl Solution for Day$PADDED_DAY {
+    const PROBLEM: Problem = P;
+    type Parsed = Vec<Vec<usize>>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
+    }
+
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        pt1(parsed)
+    }
+
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        pt2(parsed)
+    }
+}
+
+crate::examples!(Day$PADDED_DAY, pt1: [$PT1_EXAMPLES], pt2: [$PT2_EXAMPLES]);
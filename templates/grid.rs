@@ -0,0 +1,45 @@
+use crate::{error::Error, grid::Grid, Problem, Solution};
+/// problem: https://adventofcode.com/$YEAR/day/$DAY
+/// input: https://adventofcode.com/$YEAR/day/$DAY/input
+
+pub const P: Problem = Problem {
+    year: $YEAR,
+    day: $DAY,
+    name: "$NAME",
+};
+
+fn pt1(grid: &Grid<u8>) -> usize {
+    todo!()
+}
+
+fn pt2(grid: &Grid<u8>) -> usize {
+    todo!()
+}
+
+/// one row of digits per line
+fn parse(input: &str) -> Result<Grid<u8>, Error> {
+    Grid::parse_with(input, |c| c.to_digit(10).map(|d| d as u8))
+}
+
+pub struct Day$PADDED_DAY;
+
+impl Solution for Day$PADDED_DAY {
+    const PROBLEM: Problem = P;
+    type Parsed = Grid<u8>;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
+    }
+
+    fn pt1(grid: &Self::Parsed) -> Self::OutputPt1 {
+        pt1(grid)
+    }
+
+    fn pt2(grid: &Self::Parsed) -> Self::OutputPt2 {
+        pt2(grid)
+    }
+}
+
+crate::examples!(Day$PADDED_DAY, pt1: [$PT1_EXAMPLES], pt2: [$PT2_EXAMPLES]);
@@ -0,0 +1,45 @@
+use crate::{error::Error, Problem, Solution};
+/// problem: https://adventofcode.com/$YEAR/day/$DAY
+/// input: https://adventofcode.com/$YEAR/day/$DAY/input
+
+pub const P: Problem = Problem {
+    year: $YEAR,
+    day: $DAY,
+    name: "$NAME",
+};
+
+fn pt1(parsed: &str) -> usize {
+    todo!()
+}
+
+fn pt2(parsed: &str) -> usize {
+    todo!()
+}
+
+/// the whole input is a single line
+fn parse(input: &str) -> Result<String, Error> {
+    Ok(input.trim().to_owned())
+}
+
+pub struct Day$PADDED_DAY;
+
+impl Solution for Day$PADDED_DAY {
+    const PROBLEM: Problem = P;
+    type Parsed = String;
+    type OutputPt1 = usize;
+    type OutputPt2 = usize;
+
+    fn parse(data: &str) -> Result<Self::Parsed, Error> {
+        parse(data)
+    }
+
+    fn pt1(parsed: &Self::Parsed) -> Self::OutputPt1 {
+        pt1(parsed)
+    }
+
+    fn pt2(parsed: &Self::Parsed) -> Self::OutputPt2 {
+        pt2(parsed)
+    }
+}
+
+crate::examples!(Day$PADDED_DAY, pt1: [$PT1_EXAMPLES], pt2: [$PT2_EXAMPLES]);